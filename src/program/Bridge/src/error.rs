@@ -30,6 +30,98 @@ pub enum BridgeError {
     ClaimAboveDailyLimit,
     #[error("Bridge Token account key mismatch.")]
     TokenAccountKeyMismatch,
+    #[error("Invalid Ed25519 signature instruction.")]
+    InvalidSignatureInstruction,
+    #[error("Not enough validator signatures.")]
+    InsufficientValidatorSignatures,
+    #[error("Validator already added.")]
+    ValidatorAlreadyAdded,
+    #[error("Validator doesn't exist.")]
+    NonExistantValidator,
+    #[error("Validator set is full.")]
+    ValidatorSetFull,
+    #[error("Invalid validator threshold.")]
+    InvalidValidatorThreshold,
+    #[error("Chain already added.")]
+    ChainAlreadyAdded,
+    #[error("Chain is not registered or disabled.")]
+    ChainNotRegistered,
+    #[error("Chain is paused.")]
+    ChainPaused,
+    #[error("Address kind not accepted by chain.")]
+    UnsupportedAddressKind,
+    #[error("Bridge is paused.")]
+    BridgePaused,
+    #[error("Bridge is not paused.")]
+    BridgeNotPaused,
+    #[error("Role already granted.")]
+    RoleAlreadyGranted,
+    #[error("Account doesn't have the role.")]
+    MissingRole,
+    #[error("No pending owner.")]
+    NoPendingOwner,
+    #[error("Renounce not confirmed.")]
+    RenounceNotConfirmed,
+    #[error("Instruction must be queued in the timelock.")]
+    ActionMustBeQueued,
+    #[error("Instruction can't be queued in the timelock.")]
+    ActionNotTimelocked,
+    #[error("Admin action is too large.")]
+    ActionTooLarge,
+    #[error("Timelock delay has not passed.")]
+    ActionNotReady,
+    #[error("Admin action already executed or cancelled.")]
+    ActionAlreadyDone,
+    #[error("Not a signer of the owner multisig.")]
+    NotMultisigSigner,
+    #[error("Invalid owner multisig signers or threshold.")]
+    InvalidMultisigThreshold,
+    #[error("Multisig transaction already approved by signer.")]
+    AlreadyApproved,
+    #[error("Multisig transaction already executed.")]
+    TransactionAlreadyExecuted,
+    #[error("Not enough approvals for multisig transaction.")]
+    InsufficientApprovals,
+    #[error("Multisig signers changed since the transaction was proposed.")]
+    MultisigSignersChanged,
+    #[error("Multisig transaction is too large.")]
+    TransactionTooLarge,
+    #[error("Accounts don't match the multisig transaction.")]
+    TransactionAccountsMismatch,
+    #[error("Treasury is not set.")]
+    TreasuryNotSet,
+    #[error("Fee account is not owned by the treasury.")]
+    NotTreasuryAccount,
+    #[error("Cannot withdraw more than collected fees.")]
+    WithdrawAboveCollectedFees,
+    #[error("Limit window must be a nonzero multiple of the bucket count.")]
+    InvalidLimitWindow,
+    #[error("Cannot request above outbound limit.")]
+    TransferAboveOutboundLimit,
+    #[error("Cannot claim above recipient limit.")]
+    ClaimAboveRecipientLimit,
+    #[error("Pending claim already executed or cancelled.")]
+    PendingClaimAlreadyDone,
+    #[error("Pending claim can't be released yet.")]
+    PendingClaimNotReady,
+    #[error("Pending claim can only be cancelled before its release time.")]
+    PendingClaimReleased,
+    #[error("Transfer request already refunded.")]
+    AlreadyRefunded,
+    #[error("Mint is not owned by a supported token program.")]
+    UnsupportedTokenProgram,
+    #[error("Decimals too far apart to scale amounts between.")]
+    InvalidDecimals,
+    #[error("Remote token identifier is too long.")]
+    RemoteTokenTooLong,
+    #[error("Fee tiers must start at zero, increase and have rates below 10^18.")]
+    InvalidFeeSchedule,
+    #[error("Amount does not cover the fee.")]
+    AmountBelowFee,
+    #[error("Chain is already paused.")]
+    ChainAlreadyPaused,
+    #[error("Chain is not paused.")]
+    ChainNotPaused,
 }
 
 impl From<BridgeError> for ProgramError {
@@ -0,0 +1,248 @@
+use crate::fee::RoundingMode;
+use crate::state::{FeeTier, RemoteAddress, Role};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+/// Events logged by the Bridge program.
+///
+/// Each event is Borsh encoded and logged with `sol_log_data`, so it shows up in the
+/// transaction logs as a base64 encoded `Program data:` entry. Off-chain code decodes
+/// the bytes with `BridgeEvent::try_from_slice`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum BridgeEvent {
+    /// A TransferRequest locked 'amount' (fee included) for delivery on 'chain_id',
+    /// 'remote_amount' is delivered in the remote token's decimals as 'remote_token',
+    /// empty when the token has no mapping for 'chain_id'
+    TransferRequested {
+        token_index: u64,
+        from: Pubkey,
+        to: RemoteAddress,
+        amount: u64,
+        fee: u64,
+        chain_id: u64,
+        index: u64,
+        remote_amount: u64,
+        remote_token: Vec<u8>,
+    },
+    /// A RefundRequest returned 'amount' of request 'index' to its sender, 'fee_refunded'
+    /// of it out of the collected fees
+    TransferRefunded {
+        token_index: u64,
+        to: Pubkey,
+        amount: u64,
+        fee_refunded: u64,
+        index: u64,
+    },
+    /// A TransferReceipt paid out 'amount' for request 'index' of 'chain_id'
+    TransferReceived {
+        token_index: u64,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        chain_id: u64,
+        index: u64,
+    },
+    /// A TransferReceipt at or above the token's settlement threshold, released by
+    /// ExecutePendingClaim from 'release_time'
+    PendingClaimQueued {
+        token_index: u64,
+        from: Pubkey,
+        to: Pubkey,
+        amount: u64,
+        chain_id: u64,
+        index: u64,
+        release_time: u64,
+    },
+    PendingClaimCancelled {
+        chain_id: u64,
+        index: u64,
+    },
+    FeesWithdrawn {
+        token_index: u64,
+        to: Pubkey,
+        amount: u64,
+    },
+    StableFeeUpdateScheduled {
+        new_stable_fee: u64,
+        stable_fee_update_time: u64,
+    },
+    TokenFeeUpdateScheduled {
+        token_index: u64,
+        new_fee: u64,
+        fee_update_time: u64,
+    },
+    FeeScheduleUpdateScheduled {
+        token_index: u64,
+        tiers: Vec<FeeTier>,
+        min_fee: u64,
+        max_fee: u64,
+        update_time: u64,
+    },
+    TokenLimitUpdated {
+        token_index: u64,
+        limit: u64,
+    },
+    SettlementUpdated {
+        token_index: u64,
+        threshold: u64,
+        delay: u64,
+    },
+    RemoteDecimalsUpdated {
+        token_index: u64,
+        local_decimals: u8,
+        remote_decimals: u8,
+    },
+    RemoteTokenUpdated {
+        token_index: u64,
+        chain_id: u64,
+        remote_token: Vec<u8>,
+    },
+    RecipientLimitUpdated {
+        token_index: u64,
+        limit: u64,
+    },
+    TokenLimitWindowUpdated {
+        token_index: u64,
+        window: u64,
+    },
+    OutboundLimitUpdated {
+        token_index: u64,
+        limit: u64,
+        window: u64,
+    },
+    TokenAdded {
+        token_index: u64,
+        token_address: Pubkey,
+    },
+    TokenPaused {
+        token_index: u64,
+    },
+    TokenUnpaused {
+        token_index: u64,
+    },
+    TokenMintBurnSet {
+        token_index: u64,
+        mint_burn: bool,
+    },
+    /// 'new_owner' is the zero key when ownership was renounced
+    OwnershipTransferred {
+        previous_owner: Pubkey,
+        new_owner: Pubkey,
+    },
+    OwnershipProposed {
+        owner: Pubkey,
+        pending_owner: Pubkey,
+    },
+    OwnershipProposalCancelled {
+        pending_owner: Pubkey,
+    },
+    ValidatorSetCreated {
+        validators: Vec<Pubkey>,
+        threshold: u8,
+    },
+    ValidatorAdded {
+        validator: Pubkey,
+    },
+    ValidatorRemoved {
+        validator: Pubkey,
+    },
+    ValidatorThresholdUpdated {
+        threshold: u8,
+    },
+    ChainAdded {
+        chain_id: u64,
+        stable_fee: u64,
+        address_kinds: u8,
+    },
+    ChainUpdated {
+        chain_id: u64,
+        stable_fee: u64,
+        address_kinds: u8,
+    },
+    ChainDisabled {
+        chain_id: u64,
+    },
+    /// 'new_guardian' is the zero key when the guardian was removed
+    GuardianUpdated {
+        previous_guardian: Pubkey,
+        new_guardian: Pubkey,
+    },
+    BridgePaused {
+        by: Pubkey,
+    },
+    BridgeUnpaused,
+    RoleGranted {
+        role: Role,
+        account: Pubkey,
+    },
+    RoleRevoked {
+        role: Role,
+        account: Pubkey,
+    },
+    AdminActionQueued {
+        action_id: u64,
+        proposer: Pubkey,
+        eta: u64,
+    },
+    AdminActionExecuted {
+        action_id: u64,
+    },
+    AdminActionCancelled {
+        action_id: u64,
+    },
+    TimelockDelayUpdated {
+        timelock_delay: u64,
+    },
+    OwnerMultisigUpdated {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    MultisigTransactionProposed {
+        transaction_id: u64,
+        proposer: Pubkey,
+    },
+    MultisigTransactionApproved {
+        transaction_id: u64,
+        signer: Pubkey,
+    },
+    MultisigTransactionExecuted {
+        transaction_id: u64,
+    },
+    TreasuryUpdated {
+        previous_treasury: Pubkey,
+        new_treasury: Pubkey,
+    },
+    LamportFeeUpdated {
+        token_index: u64,
+        lamport_fee: u64,
+    },
+    FeeRoundingUpdated {
+        token_index: u64,
+        rounding: RoundingMode,
+    },
+    /// A TransferRequest of 'token_index' paid 'lamport_fee' into the FeeVault
+    LamportFeeCharged {
+        token_index: u64,
+        from: Pubkey,
+        lamport_fee: u64,
+        index: u64,
+    },
+    LamportFeesWithdrawn {
+        to: Pubkey,
+        amount: u64,
+    },
+    ChainPaused {
+        chain_id: u64,
+    },
+    ChainUnpaused {
+        chain_id: u64,
+    },
+}
+
+impl BridgeEvent {
+    /// Logs the Borsh encoded event
+    pub fn emit(&self) {
+        let data = self.try_to_vec().unwrap();
+        sol_log_data(&[&data]);
+    }
+}
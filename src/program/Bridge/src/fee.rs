@@ -0,0 +1,305 @@
+use crate::error::BridgeError;
+use crate::processor::TEN_POW_18;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+
+/// Quote of a transfer, set Borsh encoded as the return data of CalculateFee
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FeeQuote {
+    /// Taken out of the amount
+    pub fee: u64,
+    /// Delivered once the fee, and the dust the remote decimals can't represent, are taken out
+    pub net_amount: u64,
+    /// Charged in lamports instead of 'fee'
+    pub lamport_fee: u64,
+}
+
+/// How a fee that falls between two token units is rounded
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub enum RoundingMode {
+    /// Towards zero, in favor of the sender
+    #[default]
+    Floor,
+    /// Away from zero, in favor of the Bridge
+    Ceil,
+    /// To the nearest unit, ties to the even one
+    HalfEven,
+}
+
+impl TryFrom<u8> for RoundingMode {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RoundingMode::Floor),
+            1 => Ok(RoundingMode::Ceil),
+            2 => Ok(RoundingMode::HalfEven),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// 'amount' times 'rate' out of TEN_POW_18, rounded with 'rounding'.
+///
+/// The product is taken in u128, which holds any u64 times u64, so only a result that
+/// doesn't fit back in a u64 overflows.
+pub fn apply_rate(amount: u64, rate: u64, rounding: RoundingMode) -> Result<u64, ProgramError> {
+    let scale = TEN_POW_18 as u128;
+    let product = amount as u128 * rate as u128;
+    let quotient = product / scale;
+    let remainder = product % scale;
+
+    let rounded = match rounding {
+        RoundingMode::Floor => quotient,
+        RoundingMode::Ceil if remainder > 0 => quotient + 1,
+        RoundingMode::Ceil => quotient,
+        RoundingMode::HalfEven => {
+            let twice_remainder = remainder * 2;
+            if twice_remainder > scale || (twice_remainder == scale && quotient & 1 == 1) {
+                quotient + 1
+            } else {
+                quotient
+            }
+        }
+    };
+    u64::try_from(rounded).map_err(|_| ProgramError::Custom(BridgeError::Overflow as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODES: [RoundingMode; 3] = [
+        RoundingMode::Floor,
+        RoundingMode::Ceil,
+        RoundingMode::HalfEven,
+    ];
+
+    const EDGE_AMOUNTS: [u64; 16] = [
+        0,
+        1,
+        2,
+        3,
+        999,
+        1_000,
+        1_000_000,
+        999_999_999,
+        1_000_000_000,
+        18_000_000_000_000_000_000,
+        TEN_POW_18 - 1,
+        TEN_POW_18,
+        TEN_POW_18 + 1,
+        u64::MAX / 2,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    const EDGE_RATES: [u64; 12] = [
+        0,
+        1,
+        2,
+        1_000,
+        1_000_000_000_000,
+        3_000_000_000_000_000,
+        TEN_POW_18 / 3,
+        TEN_POW_18 / 2,
+        TEN_POW_18 / 2 + 1,
+        TEN_POW_18 - 1,
+        TEN_POW_18,
+        2 * TEN_POW_18,
+    ];
+
+    // exact quotient and remainder of amount * rate / TEN_POW_18
+    fn exact(amount: u64, rate: u64) -> (u128, u128) {
+        let product = amount as u128 * rate as u128;
+        (product / TEN_POW_18 as u128, product % TEN_POW_18 as u128)
+    }
+
+    #[test]
+    fn rounding_mode_round_trips_through_u8() {
+        for mode in MODES {
+            assert_eq!(RoundingMode::try_from(mode as u8), Ok(mode));
+        }
+        assert_eq!(
+            RoundingMode::try_from(3),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            RoundingMode::try_from(u8::MAX),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(RoundingMode::default(), RoundingMode::Floor);
+    }
+
+    #[test]
+    fn zero_amount_or_rate_is_free() {
+        for mode in MODES {
+            for amount in EDGE_AMOUNTS {
+                assert_eq!(apply_rate(amount, 0, mode), Ok(0));
+            }
+            for rate in EDGE_RATES {
+                assert_eq!(apply_rate(0, rate, mode), Ok(0));
+            }
+        }
+    }
+
+    #[test]
+    fn whole_rate_is_the_amount() {
+        for mode in MODES {
+            for amount in EDGE_AMOUNTS {
+                assert_eq!(apply_rate(amount, TEN_POW_18, mode), Ok(amount));
+            }
+        }
+    }
+
+    #[test]
+    fn amounts_above_u64_products_do_not_overflow() {
+        // 10^19 * 10^15 overflowed the u64 product
+        let amount: u64 = 10_000_000_000_000_000_000;
+        let rate = 1_000_000_000_000_000;
+        assert!(amount.checked_mul(rate).is_none());
+        for mode in MODES {
+            assert_eq!(apply_rate(amount, rate, mode), Ok(10_000_000_000_000_000));
+        }
+        // u64::MAX - 18.44..
+        assert_eq!(
+            apply_rate(u64::MAX, TEN_POW_18 - 1, RoundingMode::Floor),
+            Ok(u64::MAX - 19)
+        );
+        assert_eq!(
+            apply_rate(u64::MAX, TEN_POW_18 - 1, RoundingMode::Ceil),
+            Ok(u64::MAX - 18)
+        );
+        assert_eq!(
+            apply_rate(u64::MAX, TEN_POW_18 - 1, RoundingMode::HalfEven),
+            Ok(u64::MAX - 18)
+        );
+    }
+
+    #[test]
+    fn results_above_u64_overflow() {
+        for mode in MODES {
+            assert_eq!(
+                apply_rate(u64::MAX, 2 * TEN_POW_18, mode),
+                Err(ProgramError::Custom(BridgeError::Overflow as u32))
+            );
+            assert_eq!(
+                apply_rate(u64::MAX, TEN_POW_18 + 1, mode),
+                Err(ProgramError::Custom(BridgeError::Overflow as u32))
+            );
+        }
+        assert_eq!(
+            apply_rate(u64::MAX / 2, 2 * TEN_POW_18, RoundingMode::Floor),
+            Ok(u64::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn floor_truncates() {
+        for amount in EDGE_AMOUNTS {
+            for rate in EDGE_RATES {
+                let (quotient, _) = exact(amount, rate);
+                match u64::try_from(quotient) {
+                    Ok(expected) => {
+                        assert_eq!(apply_rate(amount, rate, RoundingMode::Floor), Ok(expected))
+                    }
+                    Err(_) => assert!(apply_rate(amount, rate, RoundingMode::Floor).is_err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ceil_rounds_any_remainder_up() {
+        for amount in EDGE_AMOUNTS {
+            for rate in EDGE_RATES {
+                let (quotient, remainder) = exact(amount, rate);
+                let expected = quotient + u128::from(remainder > 0);
+                match u64::try_from(expected) {
+                    Ok(expected) => {
+                        assert_eq!(apply_rate(amount, rate, RoundingMode::Ceil), Ok(expected))
+                    }
+                    Err(_) => assert!(apply_rate(amount, rate, RoundingMode::Ceil).is_err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn half_even_rounds_to_nearest() {
+        for amount in EDGE_AMOUNTS {
+            for rate in EDGE_RATES {
+                let (quotient, remainder) = exact(amount, rate);
+                let half = TEN_POW_18 as u128 / 2;
+                let expected = if remainder > half || (remainder == half && quotient % 2 == 1) {
+                    quotient + 1
+                } else {
+                    quotient
+                };
+                match u64::try_from(expected) {
+                    Ok(expected) => {
+                        assert_eq!(
+                            apply_rate(amount, rate, RoundingMode::HalfEven),
+                            Ok(expected)
+                        )
+                    }
+                    Err(_) => assert!(apply_rate(amount, rate, RoundingMode::HalfEven).is_err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn half_even_ties_go_to_the_even_unit() {
+        let half = TEN_POW_18 / 2;
+        // 1 * 0.5 = 0.5 -> 0, 3 * 0.5 = 1.5 -> 2, 5 * 0.5 = 2.5 -> 2, 7 * 0.5 = 3.5 -> 4
+        assert_eq!(apply_rate(1, half, RoundingMode::HalfEven), Ok(0));
+        assert_eq!(apply_rate(3, half, RoundingMode::HalfEven), Ok(2));
+        assert_eq!(apply_rate(5, half, RoundingMode::HalfEven), Ok(2));
+        assert_eq!(apply_rate(7, half, RoundingMode::HalfEven), Ok(4));
+        // just past and just short of a tie
+        assert_eq!(apply_rate(1, half + 1, RoundingMode::HalfEven), Ok(1));
+        assert_eq!(apply_rate(1, half - 1, RoundingMode::HalfEven), Ok(0));
+        assert_eq!(
+            apply_rate(u64::MAX, half, RoundingMode::HalfEven),
+            Ok(u64::MAX / 2 + 1)
+        );
+        assert_eq!(
+            apply_rate(u64::MAX - 2, half, RoundingMode::HalfEven),
+            Ok(u64::MAX / 2 - 1)
+        );
+    }
+
+    #[test]
+    fn modes_stay_within_one_unit_and_below_the_amount() {
+        for amount in EDGE_AMOUNTS {
+            for rate in EDGE_RATES {
+                let (Ok(floor), Ok(ceil), Ok(half_even)) = (
+                    apply_rate(amount, rate, RoundingMode::Floor),
+                    apply_rate(amount, rate, RoundingMode::Ceil),
+                    apply_rate(amount, rate, RoundingMode::HalfEven),
+                ) else {
+                    continue;
+                };
+                assert!(floor <= half_even && half_even <= ceil);
+                assert!(ceil - floor <= 1);
+                if rate <= TEN_POW_18 {
+                    assert!(ceil <= amount);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_amounts_round_for_every_rate_step() {
+        // every amount up to 1000 against rates around one unit per amount
+        for amount in 1..=1_000u64 {
+            let rate = TEN_POW_18 / amount;
+            for mode in MODES {
+                let fee = apply_rate(amount, rate, mode).unwrap();
+                let (quotient, remainder) = exact(amount, rate);
+                assert!(fee as u128 == quotient || (remainder > 0 && fee as u128 == quotient + 1));
+            }
+        }
+    }
+}
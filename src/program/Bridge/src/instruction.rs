@@ -1,9 +1,12 @@
+use crate::fee::RoundingMode;
+use crate::state::{FeeTier, RemoteAddress, Role, TransactionAccount};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum BridgeInstruction {
-    /// Initialize storage accounts for Bridge
+    /// Initialize storage accounts for Bridge. 'timelock_delay' is in seconds and also
+    /// delays fee updates.
     ///
     /// Accounts expected
     /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
@@ -14,35 +17,70 @@ pub enum BridgeInstruction {
     /// 5. `[writeable]` The account used as 'token_added' dictionary
     Construct {
         w_pokt_address: Pubkey,
-        verify_address: Pubkey,
         chain_id: u64,
         stable_fee: u64,
+        timelock_delay: u64,
     },
+    /// Records the request in a TransferRequestRecord PDA keyed by Bridge's 'current_index'.
+    ///
     /// Accounts expected
-    /// 0. `[]` The account of person initializing bridge - the 'owner'.
-    /// 1. `[writeable]` The account used as global storage of Bridge program
-    /// 2. `[writeable]` The account used as 'token_list' dictionary
-    /// 3. `[]` the token mint account found at 'token_index' in token_list dictionary
-    /// 4. `[writable]` the token account of token sender
-    /// 5. `[writeable]` the CalculateFeeResult account
-    /// 6. `[writable]` the Token account of Bridge
-    /// 7. `[signer]` the sender token account's owner
+    /// 0. `[writeable]` The account used as global storage of Bridge program
+    /// 1. `[writeable]` The account used as 'token_list' dictionary
+    /// 2. `[writeable]` the token mint account found at 'token_index' in token_list dictionary
+    /// 3. `[writable]` the token account of token sender
+    /// 4. `[writable]` the Token account of Bridge
+    /// 5. `[signer, writeable]` the sender token account's owner, pays for the record account
+    /// 6. `[writeable]` the TransferRequestRecord account for Bridge's 'current_index'
+    /// 7. `[]` The System program account
+    /// 8. `[]` The ChainRegistry account of 'chain_id'
+    /// 9. `[writeable]` The OutboundLimitDictionary account of 'token_index'
+    /// 10. `[]` The RemoteTokenMapping account of ('token_index', 'chain_id')
+    /// 11. `[writeable]` The FeeVault account, only used when the token has a 'lamport_fee'
+    /// 12. `[writeable]` The FeeSchedule account of 'token_index'
+    ///
+    /// 'to' must be of a kind in the ChainRegistry's 'address_kinds'.
+    /// Tokens with a 'lamport_fee' charge it to account 5 in lamports and take no fee out of
+    /// 'amount'.
+    /// Mint/burn tokens burn 'amount' minus the fee, lock/unlock tokens lock all of 'amount'.
+    /// For Token-2022 mints with a transfer fee, the amount actually received by the Bridge is bridged,
+    /// and for mint/burn tokens only the part of the fee it receives is collected.
+    /// The amount is delivered in the token's 'remote_decimals', dust they can't represent is
+    /// left with the sender.
     TransferRequest {
         token_index: u64,
-        to: Pubkey,
+        to: RemoteAddress,
         amount: u64,
         chain_id: u64,
     },
+    /// Must be preceded in the same transaction by Ed25519 program instructions in which
+    /// at least 'threshold' distinct members of the validator set sign
+    /// `signature::transfer_receipt_message` over the program id, the Bridge's 'chain_id' and
+    /// the arguments. Any account may submit it.
+    ///
     /// Accounts expected
-    /// 0. `[writeable]` The account used as global storage of Bridge program
-    /// 1. `[writeable]` The account used as 'claimed' dictionary
-    /// 2. `[writeable]` The account used as 'token_list' dictionary
-    /// 3. `[writable]` The account used as 'daily_token_claims' dictionary
-    /// 4. `[signer]` The signatory account.
-    /// 5. `[writeable]` The token mint account for this token data's mint.
-    /// 6. `[signer, writeable]` Bridge's associated token account for this mint.
-    /// 7. `[signer, writeable]` receiver's associated token account for this mint.
-    /// 8. `[signer]` The bridge PDA account
+    /// 0. `[]` The account used as global storage of Bridge program
+    /// 1. `[]` The account used as validator set
+    /// 2. `[]` The ChainRegistry account of 'chain_id'
+    /// 3. `[writeable]` The account used as 'claimed' dictionary for (chain_id, index)
+    /// 4. `[writeable]` The account used as 'token_list' dictionary
+    /// 5. `[writable]` The account used as 'daily_token_claims' dictionary
+    /// 6. `[writable]` The RecipientClaimsDictionary account of ('token_index', 'to'),
+    ///    only read when the token has a 'recipient_limit'
+    /// 7. `[writable]` The PendingClaim account of (chain_id, index), only used when 'amount'
+    ///    reaches the token's 'settlement_threshold'
+    /// 8. `[]` The Instructions Sysvar account
+    /// 9. `[writeable]` Bridge's token account for this mint.
+    /// 10. `[writeable]` receiver's token account for this mint, owned by 'to'.
+    /// 11. `[writeable]` The token mint account for this token data's mint.
+    /// 12. `[]` The SPL Token program account
+    ///
+    /// Mint/burn tokens are minted through w_pokt's MintOnlyBridge instead, which also needs
+    /// 13. `[]` The WPokt global state account, the mint authority of the mint
+    /// 14. `[]` The w_pokt program account
+    ///
+    /// Receipts of at least the token's 'settlement_threshold' are only recorded in the
+    /// PendingClaim account, and paid out by ExecutePendingClaim after the settlement delay.
+    /// 'amount' is in the token's 'remote_decimals', scaled to the mint's decimals rounding down.
     TransferReceipt {
         token_index: u64,
         from: Pubkey,
@@ -50,40 +88,41 @@ pub enum BridgeInstruction {
         amount: u64,
         chain_id: u64,
         index: u64,
-        signature_account: Pubkey,
-    },
-    /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
-    /// 1. `[writeable]` The account used as global storage of bridge
-    UpdateVerifyAddressOnlyOwner {
-        verify_address: Pubkey,
     },
-    /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// Timelocked, queued with QueueAdminAction by a LimitManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[writable]` The account used as 'token_list' dictionary
     UpdateTokenLimitOnlyOwner {
         token_index: u64,
         limit: u64,
     },
-    /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// Sets the seconds covered by the rolling claim limit of 'token_index'.
+    /// Timelocked, queued with QueueAdminAction by a LimitManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[writable]` The account used as 'token_list' dictionary
-    SetTokenLimitTimeOnlyOwner {
+    /// 3. `[writable]` The account used as 'daily_token_claims' dictionary
+    SetTokenLimitWindowOnlyOwner {
         token_index: u64,
-        timestamp: u64,
+        window: u64,
     },
     /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 0. `[signer]` An account holding the FeeManager role
     /// 1. `[writable]` The account used as global storage of bridge
+    /// 2. `[]` The RoleDictionary account of (FeeManager, account 0)
     UpdateStableFeeOnlyOwner {
         new_stable_fee: u64,
     },
     /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 0. `[signer]` An account holding the FeeManager role
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[ writable]` The account used as 'token_list' dictionary
+    /// 3. `[]` The RoleDictionary account of (FeeManager, account 0)
     UpdateTokenFeeOnlyOwner {
         index: u64,
         new_token_fee: u64,
@@ -91,24 +130,36 @@ pub enum BridgeInstruction {
     /// Accounts expected
     /// 0. `[]` The account used as global storage of bridge
     /// 1. `[]` The account used as 'token_list' dictionary
+    /// 2. `[writable]` (optional) The FeeSchedule account of 'token_index'
     UpdateFees {
         token_index: u64,
     },
-    /// 0. `[signer, writeable]` Owner Account
-    /// 1. `[writeable]` Owner Token Account
+    /// Withdraws 'amount' of the fees collected for token 'index' to the treasury.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` An account holding the Treasurer role
+    /// 1. `[writeable]` The treasury's token account for the mint
     /// 2. `[]` Bridge Account
-    /// 3. `[signer]` Bridge Pda Account
-    /// 4. `[writeable]` Bridge Token Account, To be created in the same transaction as WithdrawFees, authority set to Bridge PDA Account
-    /// 5. `[writeable]` Mint Account at token_index
-    /// 6. `[writeable]` Token List Account
-    WithdrawFeesOnlyOwner {
+    /// 3. `[writeable]` Bridge Token Account for the mint, authority set to Bridge PDA Account
+    /// 4. `[writeable]` Mint Account at token_index
+    /// 5. `[writeable]` Token List Account
+    /// 6. `[]` The RoleDictionary account of (Treasurer, account 0)
+    WithdrawFees {
         index: u64,
+        amount: u64,
     },
-    /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// Timelocked, queued with QueueAdminAction by a TokenLister.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[writable]` The account used as 'token_list' dictionary
     /// 3. `[writable]` The account used as 'token_added' dictionary
+    /// 4. `[]` The token mint account, owned by the SPL Token or Token-2022 program
+    /// 5. `[]` The Rent Sysvar account
+    /// 6. `[]` The System program account
+    /// 7. `[writeable]` Bridge Token Account for the mint, created with its authority set to Bridge PDA Account
+    /// 8. `[]` The token program owning the mint
     AddTokenOnlyOwner {
         index: u64,
         token_address: Pubkey,
@@ -116,37 +167,47 @@ pub enum BridgeInstruction {
         limit: u64,
     },
     /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 0. `[signer]` An account holding the Pauser role
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[writable]` The account used as 'token_list' dictionary
+    /// 3. `[]` The RoleDictionary account of (Pauser, account 0)
     PauseTokenOnlyOwner {
         token_index: u64,
     },
     /// Accounts expected
-    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 0. `[signer]` An account holding the Pauser role
     /// 1. `[]` The account used as global storage of bridge
     /// 2. `[writable]` The account used as 'token_list' dictionary
-    /// 3. `[writable]` The account used as 'daily_token_claims' dictionary
-    ///
+    /// 3. `[]` The RoleDictionary account of (Pauser, account 0)
     UnpauseTokenOnlyOwner {
         token_index: u64,
     },
+    /// Quotes a TransferRequest of 'amount', setting a Borsh encoded `fee::FeeQuote` as the
+    /// return data. Transfer fees of Token-2022 mints aren't included.
+    ///
     /// Accounts expected
     /// 0. `[]` The account used as global storage of bridge
     /// 1. `[]` The account used as 'token_list' dictionary
-    /// 2. `[]` The account used as 'calculate_fee_result' account
+    /// 2. `[]` (optional) The ChainRegistry account of the destination chain
+    /// 3. `[]` (optional) The FeeSchedule account of 'token_index', after account 2
     CalculateFee {
         token_index: u64,
         amount: u64,
     },
+    /// Leaves the Bridge without an owner for good, 'confirm' must be true.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The program owner's account.
-    /// 1. `[writeable]` The account used as WPokt's global state
-    RenounceOwnership,
+    /// 1. `[writeable]` The account used as global storage of bridge
+    RenounceOwnership {
+        confirm: bool,
+    },
+    /// Proposes 'new_owner', who takes over once it signs AcceptOwnership.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The program owner's account.
-    /// 1. `[writeable]` The account used as WPokt's global state
-    TransferOwnership {
+    /// 1. `[writeable]` The account used as global storage of bridge
+    ProposeOwner {
         new_owner: Pubkey,
     },
     CreateClaimedDictionaryPdaAccount {
@@ -156,4 +217,455 @@ pub enum BridgeInstruction {
     CreateDailyTokenClaimsDictionaryPdaAccount {
         token_index: u64,
     },
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as validator set
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    CreateValidatorSetOnlyOwner {
+        validators: Vec<Pubkey>,
+        threshold: u8,
+    },
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as validator set
+    AddValidatorOnlyOwner {
+        validator: Pubkey,
+    },
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as validator set
+    RemoveValidatorOnlyOwner {
+        validator: Pubkey,
+    },
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as validator set
+    UpdateValidatorThresholdOnlyOwner {
+        threshold: u8,
+    },
+    /// Switches a token between lock/unlock (default) and mint/burn.
+    /// Timelocked, queued with QueueAdminAction by a TokenLister.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    SetTokenMintBurnOnlyOwner {
+        token_index: u64,
+        mint_burn: bool,
+    },
+    /// Registers a remote chain, or enables a disabled one again.
+    /// A non zero 'stable_fee' overrides Bridge's stable fee for requests to the chain.
+    /// 'address_kinds' masks the `state::RemoteAddress` kinds accepted as recipients.
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The ChainRegistry account of 'chain_id'
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    AddChainOnlyOwner {
+        chain_id: u64,
+        stable_fee: u64,
+        address_kinds: u8,
+    },
+    /// Sets the stable fee override and the accepted 'address_kinds' of a registered chain.
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The ChainRegistry account of 'chain_id'
+    UpdateChainOnlyOwner {
+        chain_id: u64,
+        stable_fee: u64,
+        address_kinds: u8,
+    },
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The ChainRegistry account of 'chain_id'
+    DisableChainOnlyOwner {
+        chain_id: u64,
+    },
+    /// Sets the guardian allowed to pause the Bridge, the zero key removes it.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[writable]` The account used as global storage of bridge
+    SetGuardianOnlyOwner {
+        guardian: Pubkey,
+    },
+    /// Blocks TransferRequest, TransferReceipt and fee withdrawals for all tokens.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` The guardian or the 'owner'.
+    /// 1. `[writable]` The account used as global storage of bridge
+    PauseBridge,
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[writable]` The account used as global storage of bridge
+    UnpauseBridgeOnlyOwner,
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The RoleDictionary account of ('role', 'account')
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    GrantRoleOnlyOwner {
+        role: Role,
+        account: Pubkey,
+    },
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The RoleDictionary account of ('role', 'account')
+    RevokeRoleOnlyOwner {
+        role: Role,
+        account: Pubkey,
+    },
+    /// Accounts expected:
+    /// 0. `[signer]` The pending owner's account.
+    /// 1. `[writeable]` The account used as global storage of bridge
+    AcceptOwnership,
+    /// Accounts expected:
+    /// 0. `[signer]` The program owner's account.
+    /// 1. `[writeable]` The account used as global storage of bridge
+    CancelOwnershipProposal,
+    /// Queues a timelocked instruction, executable once Bridge's 'timelock_delay' has passed.
+    /// 'instruction' is the Borsh encoded BridgeInstruction.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` The 'owner', or a holder of the role the instruction needs
+    /// 1. `[writeable]` The account used as global storage of bridge
+    /// 2. `[writeable]` The AdminAction account for Bridge's 'action_count'
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    /// 5. `[]` The RoleDictionary account of (role, account 0), for role gated instructions
+    QueueAdminAction {
+        instruction: Vec<u8>,
+    },
+    /// Runs a queued instruction after its delay. Anyone may execute it.
+    ///
+    /// Accounts expected
+    /// 0. `[writeable]` The AdminAction account of 'action_id'
+    /// 1.. The accounts the queued instruction expects when executed
+    ExecuteAdminAction {
+        action_id: u64,
+    },
+    /// Accounts expected
+    /// 0. `[signer]` The 'owner' or the guardian
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writeable]` The AdminAction account of 'action_id'
+    CancelAdminAction {
+        action_id: u64,
+    },
+    /// Also sets the delay of fee updates.
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[writeable]` The account used as global storage of bridge
+    UpdateTimelockDelayOnlyOwner {
+        timelock_delay: u64,
+    },
+    /// Creates the owner multisig. Hand it the Bridge with ProposeOwner to its authority PDA,
+    /// then a multisig transaction running AcceptOwnership.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The OwnerMultisig account
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    CreateOwnerMultisigOnlyOwner {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    /// Replaces the multisig signers. Only runs as a multisig transaction.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` The OwnerMultisig authority PDA
+    /// 1. `[writable]` The OwnerMultisig account
+    UpdateOwnerMultisig {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    /// Proposes a Borsh encoded BridgeInstruction, run with 'accounts' signed by the multisig
+    /// authority PDA. Counts as the proposer's approval.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` A multisig signer, pays for the transaction account
+    /// 1. `[writable]` The OwnerMultisig account
+    /// 2. `[writable]` The MultisigTransaction account for OwnerMultisig's 'transaction_count'
+    /// 3. `[]` The Rent Sysvar account
+    /// 4. `[]` The System program account
+    ProposeMultisigTransaction {
+        accounts: Vec<TransactionAccount>,
+        instruction: Vec<u8>,
+    },
+    /// Accounts expected
+    /// 0. `[signer]` A multisig signer
+    /// 1. `[]` The OwnerMultisig account
+    /// 2. `[writable]` The MultisigTransaction account of 'transaction_id'
+    ApproveMultisigTransaction {
+        transaction_id: u64,
+    },
+    /// Runs an approved multisig transaction. Anyone may execute it.
+    ///
+    /// Accounts expected
+    /// 0. `[]` The OwnerMultisig account
+    /// 1. `[writable]` The MultisigTransaction account of 'transaction_id'
+    /// 2. `[]` The Bridge program account
+    /// 3. ... The accounts stored in the transaction, in order
+    ExecuteMultisigTransaction {
+        transaction_id: u64,
+    },
+    /// Sets the owner of the token accounts fees are withdrawn to.
+    /// Timelocked, queued with QueueAdminAction by the 'owner'.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[writeable]` The account used as global storage of bridge
+    SetTreasuryOnlyOwner {
+        treasury: Pubkey,
+    },
+    /// Withdraws all fees collected for each of 'indices' to the treasury.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` An account holding the Treasurer role
+    /// 1. `[]` Bridge Account
+    /// 2. `[]` The RoleDictionary account of (Treasurer, account 0)
+    /// 3. ... For each index, in order:
+    ///    - `[writeable]` The treasury's token account for the mint
+    ///    - `[writeable]` Bridge Token Account for the mint
+    ///    - `[]` Mint Account at the index
+    ///    - `[writeable]` Token List Account
+    WithdrawFeesBatch {
+        indices: Vec<u64>,
+    },
+    /// Caps what TransferRequest can send for 'token_index' to 'limit' per 'window' seconds,
+    /// a 'limit' of zero removes the cap.
+    /// Timelocked, queued with QueueAdminAction by a LimitManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[]` The account used as 'token_list' dictionary
+    /// 3. `[writable]` The OutboundLimitDictionary account of 'token_index'
+    /// 4. `[]` The Rent Sysvar account
+    /// 5. `[]` The System program account
+    SetOutboundLimitOnlyOwner {
+        token_index: u64,
+        limit: u64,
+        window: u64,
+    },
+    /// Caps what one recipient can claim of 'token_index' within its 'limit_window',
+    /// a 'limit' of zero removes the cap.
+    /// Timelocked, queued with QueueAdminAction by a LimitManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    UpdateRecipientLimitOnlyOwner {
+        token_index: u64,
+        limit: u64,
+    },
+    /// Accounts expected
+    /// 0. `[signer, writeable]` The account paying for the new account
+    /// 1. `[writeable]` The RecipientClaimsDictionary account of ('token_index', 'recipient')
+    /// 2. `[]` The Rent Sysvar account
+    /// 3. `[]` The System program account
+    CreateRecipientClaimsDictionaryPdaAccount {
+        token_index: u64,
+        recipient: Pubkey,
+    },
+    /// Receipts of 'token_index' of at least 'threshold' wait 'delay' seconds before they are
+    /// paid out, a 'threshold' of zero pays all receipts out at once.
+    /// Timelocked, queued with QueueAdminAction by a LimitManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    SetSettlementOnlyOwner {
+        token_index: u64,
+        threshold: u64,
+        delay: u64,
+    },
+    /// Created before the TransferReceipt of a receipt that will be delayed.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` The account paying for the new account
+    /// 1. `[writeable]` The PendingClaim account of ('chain_id', 'index')
+    /// 2. `[]` The Rent Sysvar account
+    /// 3. `[]` The System program account
+    CreatePendingClaimPdaAccount {
+        chain_id: u64,
+        index: u64,
+    },
+    /// Pays out a pending claim once its release time has passed. Anyone may execute it.
+    ///
+    /// Accounts expected
+    /// 0. `[]` The account used as global storage of Bridge program
+    /// 1. `[writable]` The PendingClaim account of ('chain_id', 'index')
+    /// 2. `[]` The account used as 'token_list' dictionary for the claim's token
+    /// 3. `[writeable]` Bridge's token account for this mint.
+    /// 4. `[writeable]` receiver's token account for this mint, owned by the claim's 'to'.
+    /// 5. `[writeable]` The token mint account for this token data's mint.
+    /// 6. `[]` The SPL Token program account
+    /// 7. `[]` The WPokt global state account, for mint/burn tokens only
+    /// 8. `[]` The w_pokt program account, for mint/burn tokens only
+    ExecutePendingClaim {
+        chain_id: u64,
+        index: u64,
+    },
+    /// Cancels a pending claim before its release time. Its receipt stays claimed, and its
+    /// amount stays counted against the token's and the recipient's claim limits until it
+    /// leaves their windows.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` The guardian or the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The PendingClaim account of ('chain_id', 'index')
+    CancelPendingClaim {
+        chain_id: u64,
+        index: u64,
+    },
+    /// Returns the tokens of TransferRequest 'index' to its sender after its delivery was
+    /// rejected, fee included when 'refund_fee' is set and none of the token's fees were
+    /// withdrawn since the request. Must be preceded in the same transaction by Ed25519 program instructions in
+    /// which at least 'threshold' distinct members of the validator set sign
+    /// `signature::refund_request_message` over the program id, the Bridge's 'chain_id' and
+    /// the arguments. Any account may submit it.
+    ///
+    /// Accounts expected
+    /// 0. `[]` The account used as global storage of Bridge program
+    /// 1. `[]` The account used as validator set
+    /// 2. `[writable]` The TransferRequestRecord account of 'index'
+    /// 3. `[writable]` The account used as 'token_list' dictionary for the request's token
+    /// 4. `[]` The Instructions Sysvar account
+    /// 5. `[writeable]` Bridge's token account for this mint.
+    /// 6. `[writeable]` The sender's token account for this mint.
+    /// 7. `[writeable]` The token mint account of the request.
+    /// 8. `[]` The SPL Token program account
+    /// 9. `[]` The WPokt global state account, for mint/burn tokens only
+    /// 10. `[]` The w_pokt program account, for mint/burn tokens only
+    ///
+    /// Mint/burn tokens mint the burned amount back, the fee is unlocked from the Bridge.
+    /// Lamport fees stay in the FeeVault.
+    RefundRequest {
+        index: u64,
+        refund_fee: bool,
+    },
+    /// Amounts of 'token_index' are scaled between the mint's decimals and 'remote_decimals'
+    /// when bridged. Tokens start out with the mint's decimals on both sides.
+    /// Timelocked, queued with QueueAdminAction by a TokenLister.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    SetRemoteDecimalsOnlyOwner {
+        token_index: u64,
+        remote_decimals: u8,
+    },
+    /// Maps 'token_index' to its identifier on 'chain_id', at most
+    /// `state::MAX_REMOTE_TOKEN_LEN` bytes. An empty 'remote_token' clears the mapping.
+    /// Timelocked, queued with QueueAdminAction by a TokenLister.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[]` The account used as 'token_list' dictionary
+    /// 3. `[writable]` The RemoteTokenMapping account of ('token_index', 'chain_id')
+    /// 4. `[]` The Rent Sysvar account
+    /// 5. `[]` The System program account
+    SetRemoteTokenOnlyOwner {
+        token_index: u64,
+        chain_id: u64,
+        remote_token: Vec<u8>,
+    },
+    /// TransferRequests of 'token_index' pay 'lamport_fee' lamports into the FeeVault instead
+    /// of a fee out of the bridged amount, a 'lamport_fee' of zero goes back to the latter.
+    /// Timelocked, queued with QueueAdminAction by a FeeManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer, writeable]` The account executing the queued action, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    /// 3. `[writable]` The FeeVault account
+    /// 4. `[]` The Rent Sysvar account
+    /// 5. `[]` The System program account
+    SetLamportFeeOnlyOwner {
+        token_index: u64,
+        lamport_fee: u64,
+    },
+    /// Withdraws 'amount' of the lamport fees in the FeeVault to the treasury.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` An account holding the Treasurer role
+    /// 1. `[]` Bridge Account
+    /// 2. `[writable]` The FeeVault account
+    /// 3. `[writable]` The treasury account
+    /// 4. `[]` The RoleDictionary account of (Treasurer, account 0)
+    WithdrawLamportFees {
+        amount: u64,
+    },
+    /// Schedules 'tiers' of 'token_index', at most `state::MAX_FEE_TIERS`, to replace its fee
+    /// after the Bridge's fee update duration. The fee of an amount is the rate of the last
+    /// tier it reaches, bounded by 'min_fee' and a non zero 'max_fee'. No tiers goes back to
+    /// the token's proportional fee.
+    ///
+    /// Accounts expected
+    /// 0. `[signer, writeable]` An account holding the FeeManager role, pays for new accounts
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[]` The account used as 'token_list' dictionary
+    /// 3. `[writable]` The FeeSchedule account of 'token_index'
+    /// 4. `[]` The RoleDictionary account of (FeeManager, account 0)
+    /// 5. `[]` The Rent Sysvar account
+    /// 6. `[]` The System program account
+    UpdateFeeScheduleOnlyOwner {
+        token_index: u64,
+        tiers: Vec<FeeTier>,
+        min_fee: u64,
+        max_fee: u64,
+    },
+    /// Rounds the fees of 'token_index' that fall between two token units with 'rounding'.
+    /// Tokens start out rounding down.
+    /// Timelocked, queued with QueueAdminAction by a FeeManager.
+    ///
+    /// Accounts expected when executed
+    /// 0. `[signer]` The account executing the queued action
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The account used as 'token_list' dictionary
+    SetFeeRoundingOnlyOwner {
+        token_index: u64,
+        rounding: RoundingMode,
+    },
+    /// Pauses or unpauses TransferRequest and TransferReceipt for a registered chain.
+    ///
+    /// Accounts expected
+    /// 0. `[signer]` The account of person initializing bridge - the 'owner'.
+    /// 1. `[]` The account used as global storage of bridge
+    /// 2. `[writable]` The ChainRegistry account of 'chain_id'
+    SetChainPausedOnlyOwner {
+        chain_id: u64,
+        paused: bool,
+    },
 }
@@ -1,5 +1,8 @@
 pub mod entrypoint;
 pub mod error;
+pub mod events;
+pub mod fee;
 pub mod instruction;
 pub mod processor;
+pub mod signature;
 pub mod state;
@@ -1,19 +1,32 @@
 // use crate::error::BridgeError;
 use crate::error::BridgeError;
+use crate::events::BridgeEvent;
+use crate::fee::{apply_rate, FeeQuote, RoundingMode};
 use crate::instruction::BridgeInstruction;
-use crate::state::CalcuateFeeResult;
+use crate::signature::{
+    refund_request_message, transfer_receipt_message, verified_signers, Receipt,
+};
 use crate::state::{
-    Bridge, ClaimedDictionary, DailyTokenClaimsDictionary, TokenAddedDictionary,
-    TokenListDictionary, COMMON_BASE_SEED,
+    decimals_factor, AdminAction, Bridge, ChainRegistry, ClaimedDictionary,
+    DailyTokenClaimsDictionary, FeeSchedule, FeeTier, FeeVault, MultisigTransaction,
+    OutboundLimitDictionary, OwnerMultisig, PendingClaim, RateLimitWindow,
+    RecipientClaimsDictionary, RemoteAddress, RemoteTokenMapping, Role, RoleDictionary,
+    TokenAddedDictionary, TokenListDictionary, TransactionAccount, TransferRequestRecord,
+    ValidatorSet, COMMON_BASE_SEED, MAX_ADMIN_ACTION_LEN, MAX_FEE_TIERS, MAX_MULTISIG_SIGNERS,
+    MAX_MULTISIG_TRANSACTION_ACCOUNTS, MAX_MULTISIG_TRANSACTION_LEN, MAX_REMOTE_TOKEN_LEN,
+    MAX_VALIDATORS,
 };
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::program_pack::Pack;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     clock::{Clock, SECONDS_PER_DAY},
     entrypoint::ProgramResult,
-    msg, program,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::{self, set_return_data},
     program_error::ProgramError,
+    program_option::COption,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
@@ -21,6 +34,8 @@ use solana_program::{
 };
 
 use spl_token;
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use w_pokt::instruction::WPoktInstruction;
 
 pub const TEN_POW_18: u64 = 1000000000000000000;
 
@@ -37,18 +52,18 @@ impl Processor {
         match instruction {
             BridgeInstruction::Construct {
                 w_pokt_address,
-                verify_address,
                 chain_id,
                 stable_fee,
+                timelock_delay,
             } => {
                 msg!("BridgeInstruction::Construct");
                 construct(
                     program_id,
                     accounts,
                     &w_pokt_address,
-                    &verify_address,
                     &chain_id,
                     &stable_fee,
+                    &timelock_delay,
                 )
             }
             BridgeInstruction::TransferRequest {
@@ -67,30 +82,18 @@ impl Processor {
                 amount,
                 chain_id,
                 index,
-                signature_account,
             } => transfer_receipt(
                 program_id,
                 accounts,
-                token_index,
-                &from,
-                &to,
-                amount,
-                chain_id,
-                index,
-                &signature_account,
+                &Receipt {
+                    token_index,
+                    from,
+                    to,
+                    amount,
+                    chain_id,
+                    index,
+                },
             ),
-            BridgeInstruction::UpdateVerifyAddressOnlyOwner { verify_address } => {
-                update_verify_address(program_id, accounts, &verify_address)
-            }
-
-            BridgeInstruction::UpdateTokenLimitOnlyOwner { token_index, limit } => {
-                update_token_limit(program_id, accounts, token_index, limit)
-            }
-
-            BridgeInstruction::SetTokenLimitTimeOnlyOwner {
-                token_index,
-                timestamp,
-            } => set_token_limit_time(program_id, accounts, token_index, timestamp),
             BridgeInstruction::UpdateStableFeeOnlyOwner { new_stable_fee } => {
                 update_stable_fee(program_id, accounts, new_stable_fee)
             }
@@ -102,15 +105,22 @@ impl Processor {
             BridgeInstruction::UpdateFees { token_index } => {
                 update_fees(program_id, accounts, token_index)
             }
-            BridgeInstruction::WithdrawFeesOnlyOwner { index } => {
-                withdraw_fees(program_id, accounts, index)
+            BridgeInstruction::UpdateFeeScheduleOnlyOwner {
+                token_index,
+                tiers,
+                min_fee,
+                max_fee,
+            } => {
+                msg!("BridgeInstruction::UpdateFeeScheduleOnlyOwner");
+                update_fee_schedule(program_id, accounts, token_index, &tiers, min_fee, max_fee)
+            }
+            BridgeInstruction::WithdrawFees { index, amount } => {
+                withdraw_fees(program_id, accounts, index, amount)
+            }
+            BridgeInstruction::WithdrawLamportFees { amount } => {
+                msg!("BridgeInstruction::WithdrawLamportFees");
+                withdraw_lamport_fees(program_id, accounts, amount)
             }
-            BridgeInstruction::AddTokenOnlyOwner {
-                index,
-                token_address,
-                fee,
-                limit,
-            } => add_token(program_id, accounts, index, token_address, fee, limit),
             BridgeInstruction::PauseTokenOnlyOwner { token_index } => {
                 pause_token(program_id, accounts, token_index)
             }
@@ -121,9 +131,11 @@ impl Processor {
                 token_index,
                 amount,
             } => calculate_fee(program_id, accounts, token_index, amount),
-            BridgeInstruction::RenounceOwnership => renounce_ownership(program_id, accounts),
-            BridgeInstruction::TransferOwnership { new_owner } => {
-                transfer_ownership(program_id, accounts, new_owner)
+            BridgeInstruction::RenounceOwnership { confirm } => {
+                renounce_ownership(program_id, accounts, confirm)
+            }
+            BridgeInstruction::ProposeOwner { new_owner } => {
+                propose_owner(program_id, accounts, new_owner)
             }
             BridgeInstruction::CreateClaimedDictionaryPdaAccount { index, chain_id } => {
                 msg!("BridgeInstruction::CreateClaimedDictionaryPdaAccount");
@@ -133,6 +145,218 @@ impl Processor {
                 msg!("BridgeInstruction::CreateDailyTokenClaimsDictionaryPdaAccount");
                 create_daily_token_claims_dictionary_pda_account(program_id, accounts, token_index)
             }
+            BridgeInstruction::CreatePendingClaimPdaAccount { chain_id, index } => {
+                msg!("BridgeInstruction::CreatePendingClaimPdaAccount");
+                create_pending_claim_pda_account(program_id, accounts, chain_id, index)
+            }
+            BridgeInstruction::ExecutePendingClaim { chain_id, index } => {
+                msg!("BridgeInstruction::ExecutePendingClaim");
+                execute_pending_claim(program_id, accounts, chain_id, index)
+            }
+            BridgeInstruction::CancelPendingClaim { chain_id, index } => {
+                cancel_pending_claim(program_id, accounts, chain_id, index)
+            }
+            BridgeInstruction::RefundRequest { index, refund_fee } => {
+                msg!("BridgeInstruction::RefundRequest");
+                refund_request(program_id, accounts, index, refund_fee)
+            }
+            BridgeInstruction::CreateRecipientClaimsDictionaryPdaAccount {
+                token_index,
+                recipient,
+            } => {
+                msg!("BridgeInstruction::CreateRecipientClaimsDictionaryPdaAccount");
+                create_recipient_claims_dictionary_pda_account(
+                    program_id,
+                    accounts,
+                    token_index,
+                    recipient,
+                )
+            }
+            BridgeInstruction::CreateValidatorSetOnlyOwner {
+                validators,
+                threshold,
+            } => {
+                msg!("BridgeInstruction::CreateValidatorSetOnlyOwner");
+                create_validator_set(program_id, accounts, &validators, threshold)
+            }
+            BridgeInstruction::SetChainPausedOnlyOwner { chain_id, paused } => {
+                set_chain_paused(program_id, accounts, chain_id, paused)
+            }
+            BridgeInstruction::DisableChainOnlyOwner { chain_id } => {
+                disable_chain(program_id, accounts, chain_id)
+            }
+            BridgeInstruction::SetGuardianOnlyOwner { guardian } => {
+                set_guardian(program_id, accounts, guardian)
+            }
+            BridgeInstruction::PauseBridge => {
+                msg!("BridgeInstruction::PauseBridge");
+                pause_bridge(program_id, accounts)
+            }
+            BridgeInstruction::UnpauseBridgeOnlyOwner => unpause_bridge(program_id, accounts),
+            BridgeInstruction::RevokeRoleOnlyOwner { role, account } => {
+                revoke_role(program_id, accounts, role, account)
+            }
+            BridgeInstruction::AcceptOwnership => accept_ownership(program_id, accounts),
+            BridgeInstruction::CancelOwnershipProposal => {
+                cancel_ownership_proposal(program_id, accounts)
+            }
+            BridgeInstruction::QueueAdminAction { instruction } => {
+                msg!("BridgeInstruction::QueueAdminAction");
+                queue_admin_action(program_id, accounts, instruction)
+            }
+            BridgeInstruction::ExecuteAdminAction { action_id } => {
+                msg!("BridgeInstruction::ExecuteAdminAction");
+                execute_admin_action(program_id, accounts, action_id)
+            }
+            BridgeInstruction::CancelAdminAction { action_id } => {
+                cancel_admin_action(program_id, accounts, action_id)
+            }
+            BridgeInstruction::CreateOwnerMultisigOnlyOwner { signers, threshold } => {
+                msg!("BridgeInstruction::CreateOwnerMultisigOnlyOwner");
+                create_owner_multisig(program_id, accounts, &signers, threshold)
+            }
+            BridgeInstruction::UpdateOwnerMultisig { signers, threshold } => {
+                update_owner_multisig(program_id, accounts, &signers, threshold)
+            }
+            BridgeInstruction::ProposeMultisigTransaction {
+                accounts: transaction_accounts,
+                instruction,
+            } => {
+                msg!("BridgeInstruction::ProposeMultisigTransaction");
+                propose_multisig_transaction(
+                    program_id,
+                    accounts,
+                    transaction_accounts,
+                    instruction,
+                )
+            }
+            BridgeInstruction::ApproveMultisigTransaction { transaction_id } => {
+                approve_multisig_transaction(program_id, accounts, transaction_id)
+            }
+            BridgeInstruction::ExecuteMultisigTransaction { transaction_id } => {
+                msg!("BridgeInstruction::ExecuteMultisigTransaction");
+                execute_multisig_transaction(program_id, accounts, transaction_id)
+            }
+            BridgeInstruction::WithdrawFeesBatch { indices } => {
+                msg!("BridgeInstruction::WithdrawFeesBatch");
+                withdraw_fees_batch(program_id, accounts, &indices)
+            }
+            BridgeInstruction::UpdateTokenLimitOnlyOwner { .. }
+            | BridgeInstruction::SetTokenLimitWindowOnlyOwner { .. }
+            | BridgeInstruction::AddTokenOnlyOwner { .. }
+            | BridgeInstruction::AddValidatorOnlyOwner { .. }
+            | BridgeInstruction::RemoveValidatorOnlyOwner { .. }
+            | BridgeInstruction::UpdateValidatorThresholdOnlyOwner { .. }
+            | BridgeInstruction::SetTokenMintBurnOnlyOwner { .. }
+            | BridgeInstruction::AddChainOnlyOwner { .. }
+            | BridgeInstruction::UpdateChainOnlyOwner { .. }
+            | BridgeInstruction::GrantRoleOnlyOwner { .. }
+            | BridgeInstruction::UpdateTimelockDelayOnlyOwner { .. }
+            | BridgeInstruction::SetTreasuryOnlyOwner { .. }
+            | BridgeInstruction::SetOutboundLimitOnlyOwner { .. }
+            | BridgeInstruction::UpdateRecipientLimitOnlyOwner { .. }
+            | BridgeInstruction::SetSettlementOnlyOwner { .. }
+            | BridgeInstruction::SetRemoteDecimalsOnlyOwner { .. }
+            | BridgeInstruction::SetRemoteTokenOnlyOwner { .. }
+            | BridgeInstruction::SetLamportFeeOnlyOwner { .. }
+            | BridgeInstruction::SetFeeRoundingOnlyOwner { .. } => {
+                Err(ProgramError::Custom(BridgeError::ActionMustBeQueued as u32))
+            }
+        }
+    }
+
+    /// Runs an instruction taken from an executed AdminAction
+    fn process_admin_action(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        instruction: BridgeInstruction,
+    ) -> ProgramResult {
+        match instruction {
+            BridgeInstruction::UpdateTokenLimitOnlyOwner { token_index, limit } => {
+                update_token_limit(program_id, accounts, token_index, limit)
+            }
+            BridgeInstruction::UpdateRecipientLimitOnlyOwner { token_index, limit } => {
+                update_recipient_limit(program_id, accounts, token_index, limit)
+            }
+            BridgeInstruction::SetSettlementOnlyOwner {
+                token_index,
+                threshold,
+                delay,
+            } => set_settlement(program_id, accounts, token_index, threshold, delay),
+            BridgeInstruction::SetTokenLimitWindowOnlyOwner {
+                token_index,
+                window,
+            } => set_token_limit_window(program_id, accounts, token_index, window),
+            BridgeInstruction::AddTokenOnlyOwner {
+                index,
+                token_address,
+                fee,
+                limit,
+            } => add_token(program_id, accounts, index, token_address, fee, limit),
+            BridgeInstruction::AddValidatorOnlyOwner { validator } => {
+                add_validator(program_id, accounts, validator)
+            }
+            BridgeInstruction::RemoveValidatorOnlyOwner { validator } => {
+                remove_validator(program_id, accounts, validator)
+            }
+            BridgeInstruction::UpdateValidatorThresholdOnlyOwner { threshold } => {
+                update_validator_threshold(program_id, accounts, threshold)
+            }
+            BridgeInstruction::SetTokenMintBurnOnlyOwner {
+                token_index,
+                mint_burn,
+            } => set_token_mint_burn(program_id, accounts, token_index, mint_burn),
+            BridgeInstruction::SetRemoteDecimalsOnlyOwner {
+                token_index,
+                remote_decimals,
+            } => set_remote_decimals(program_id, accounts, token_index, remote_decimals),
+            BridgeInstruction::SetRemoteTokenOnlyOwner {
+                token_index,
+                chain_id,
+                remote_token,
+            } => set_remote_token(program_id, accounts, token_index, chain_id, remote_token),
+            BridgeInstruction::SetLamportFeeOnlyOwner {
+                token_index,
+                lamport_fee,
+            } => set_lamport_fee(program_id, accounts, token_index, lamport_fee),
+            BridgeInstruction::SetFeeRoundingOnlyOwner {
+                token_index,
+                rounding,
+            } => set_fee_rounding(program_id, accounts, token_index, rounding),
+            BridgeInstruction::AddChainOnlyOwner {
+                chain_id,
+                stable_fee,
+                address_kinds,
+            } => {
+                msg!("BridgeInstruction::AddChainOnlyOwner");
+                add_chain(program_id, accounts, chain_id, stable_fee, address_kinds)
+            }
+            BridgeInstruction::UpdateChainOnlyOwner {
+                chain_id,
+                stable_fee,
+                address_kinds,
+            } => update_chain(program_id, accounts, chain_id, stable_fee, address_kinds),
+            BridgeInstruction::GrantRoleOnlyOwner { role, account } => {
+                msg!("BridgeInstruction::GrantRoleOnlyOwner");
+                grant_role(program_id, accounts, role, account)
+            }
+            BridgeInstruction::UpdateTimelockDelayOnlyOwner { timelock_delay } => {
+                update_timelock_delay(program_id, accounts, timelock_delay)
+            }
+            BridgeInstruction::SetTreasuryOnlyOwner { treasury } => {
+                set_treasury(program_id, accounts, treasury)
+            }
+            BridgeInstruction::SetOutboundLimitOnlyOwner {
+                token_index,
+                limit,
+                window,
+            } => {
+                msg!("BridgeInstruction::SetOutboundLimitOnlyOwner");
+                set_outbound_limit(program_id, accounts, token_index, limit, window)
+            }
+            _ => Err(ProgramError::Custom(
+                BridgeError::ActionNotTimelocked as u32,
+            )),
         }
     }
 }
@@ -199,9 +423,9 @@ fn construct(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _w_pokt_address: &Pubkey,
-    _verify_address: &Pubkey,
     _chain_id: &u64,
     _stable_fee: &u64,
+    _timelock_delay: &u64,
 ) -> ProgramResult {
     let account_info_iter = &mut _accounts.iter();
     let owner_account = next_account_info(account_info_iter)?;
@@ -223,6 +447,7 @@ fn construct(
     if !w_pokt_mint_account.key.eq(_w_pokt_address) {
         return Err(ProgramError::InvalidArgument);
     }
+    let w_pokt_decimals = mint_decimals(w_pokt_mint_account)?;
 
     // create Bridge PDA account
     let (bridge_pda, bridge_bump, bridge_seed1, bridge_seed2) =
@@ -251,10 +476,10 @@ fn construct(
     // initialize bridge pda account
     let mut bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
     bridge_data.owner = *owner_account.key;
-    bridge_data.fee_update_duration = 1;
+    bridge_data.fee_update_duration = *_timelock_delay;
+    bridge_data.timelock_delay = *_timelock_delay;
     bridge_data.stable_fee = *_stable_fee;
     bridge_data.chain_id = *_chain_id;
-    bridge_data.verify_address = *_verify_address;
     bridge_data.is_initialized = true;
     bridge_data.current_index += 1;
     bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
@@ -324,8 +549,6 @@ fn construct(
         ]],
     )?;
 
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
     let token_data_list = TokenListDictionary {
         is_initialized: true,
         token_address: *_w_pokt_address,
@@ -339,10 +562,20 @@ fn construct(
         fee_update_time: 0,
         // new fee
         new_fee: 0,
-        // daily limit
+        // claim limit
         limit: 0,
-        // daily limit time
-        limit_timestamp: current_timestamp as u64 + SECONDS_PER_DAY,
+        // claim limit window
+        limit_window: SECONDS_PER_DAY,
+        mint_burn: false,
+        recipient_limit: 0,
+        settlement_threshold: 0,
+        settlement_delay: 0,
+        token_program: spl_token::id(),
+        local_decimals: w_pokt_decimals,
+        remote_decimals: w_pokt_decimals,
+        lamport_fee: 0,
+        fee_rounding: RoundingMode::Floor,
+        fees_withdrawn_index: 0,
     };
     token_data_list.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
 
@@ -408,7 +641,7 @@ fn transfer_request(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _token_index: u64,
-    _to: Pubkey,
+    _to: RemoteAddress,
     _amount: u64,
     _chain_id: u64,
 ) -> ProgramResult {
@@ -418,15 +651,28 @@ fn transfer_request(
     let token_list_account = next_account_info(account_info_iter)?;
     let mint_account = next_account_info(account_info_iter)?;
     let source_account = next_account_info(account_info_iter)?;
-    let calculate_fee_result_account = next_account_info(account_info_iter)?;
     let bridge_token_account = next_account_info(account_info_iter)?;
     let source_auth_account = next_account_info(account_info_iter)?;
+    let transfer_request_record_account = next_account_info(account_info_iter)?; // PDA account
+    let system_program_account = next_account_info(account_info_iter)?;
+    let chain_registry_account = next_account_info(account_info_iter)?;
+    let outbound_limit_account = next_account_info(account_info_iter)?; // PDA account
+    let remote_token_account = next_account_info(account_info_iter)?; // PDA account
+    let fee_vault_account = next_account_info(account_info_iter)?; // PDA account
+    let fee_schedule_account = next_account_info(account_info_iter)?; // PDA account
 
     if !source_auth_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    when_not_paused(&bridge_data)?;
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
@@ -441,25 +687,253 @@ fn transfer_request(
         return Err(ProgramError::Custom(BridgeError::RequestToSameChain as u32));
     }
 
+    let chain_registry_data =
+        unpack_chain_registry(_program_id, chain_registry_account, _chain_id)?;
+    if !_to.is_valid() || chain_registry_data.address_kinds & _to.kind() == 0 {
+        return Err(ProgramError::Custom(
+            BridgeError::UnsupportedAddressKind as u32,
+        ));
+    }
+
+    // tokens without an OutboundLimitDictionary account have no outbound limit
+    let (outbound_limit_pda, _) =
+        OutboundLimitDictionary::generate_pda_key(_program_id, _token_index);
+    if !outbound_limit_account.key.eq(&outbound_limit_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !outbound_limit_account.data_is_empty() {
+        verify_program_accounts_ownership(
+            _program_id,
+            std::slice::from_ref(outbound_limit_account),
+        )?;
+        let mut outbound_limit_data =
+            OutboundLimitDictionary::unpack_from_slice(&outbound_limit_account.data.borrow())?;
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        outbound_limit_data
+            .transfers
+            .roll(current_timestamp, outbound_limit_data.window);
+        if outbound_limit_data.limit > 0
+            && outbound_limit_data
+                .transfers
+                .exceeds(_amount, outbound_limit_data.limit)
+        {
+            return Err(ProgramError::Custom(
+                BridgeError::TransferAboveOutboundLimit as u32,
+            ));
+        }
+        outbound_limit_data.transfers.record(_amount)?;
+        outbound_limit_data.pack_into_slice(&mut outbound_limit_account.data.borrow_mut()[..]);
+    }
+
+    // tokens without a RemoteTokenMapping account for the chain are requested with an
+    // empty 'remote_token'
+    let (remote_token_pda, _) =
+        RemoteTokenMapping::generate_pda_key(_program_id, _token_index, _chain_id);
+    if !remote_token_account.key.eq(&remote_token_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let remote_token = if remote_token_account.data_is_empty() {
+        Vec::new()
+    } else {
+        verify_program_accounts_ownership(_program_id, std::slice::from_ref(remote_token_account))?;
+        RemoteTokenMapping::unpack_from_slice(&remote_token_account.data.borrow())?.remote_token
+    };
+
+    let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(_program_id, mint_account.key);
+    if !bridge_token_account.key.eq(&bridge_token_pda) {
+        return Err(ProgramError::Custom(
+            BridgeError::TokenAccountKeyMismatch as u32,
+        ));
+    }
+
+    let token_program = token_list_data.token_program;
+    let decimals = mint_decimals(mint_account)?;
+    let lock = |amount: u64| -> ProgramResult {
+        let transfer_from_ix = spl_token_2022::instruction::transfer_checked(
+            &token_program,
+            source_account.key,
+            mint_account.key,
+            bridge_token_account.key,
+            source_auth_account.key,
+            &[source_auth_account.key],
+            amount,
+            decimals,
+        )?;
+        // source auth account can also be the delegate
+        program::invoke(
+            &transfer_from_ix,
+            &[
+                source_account.clone(),
+                mint_account.clone(),
+                bridge_token_account.clone(),
+                source_auth_account.clone(),
+            ],
+        )
+    };
+
+    // lock/unlock tokens are locked first, so a transfer fee charged by the mint comes off
+    // the bridged amount and not out of the Bridge's balance
+    let bridged_amount = if token_list_data.mint_burn {
+        _amount
+    } else {
+        let balance_before = unpack_token_account(bridge_token_account)?.amount;
+        lock(_amount)?;
+        unpack_token_account(bridge_token_account)?
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?
+    };
+
+    // tokens without a FeeSchedule account are charged their proportional fee
+    let (fee_schedule_pda, _) = FeeSchedule::generate_pda_key(_program_id, _token_index);
+    if !fee_schedule_account.key.eq(&fee_schedule_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let fee_schedule_accounts = if fee_schedule_account.data_is_empty() {
+        vec![]
+    } else {
+        vec![fee_schedule_account.clone()]
+    };
+
     update_fees(
-        _program_id,
-        &[bridge_account.clone(), token_list_account.clone()],
-        _token_index,
-    )?;
-    // get the fee calculated
-    calculate_fee(
         _program_id,
         &[
-            bridge_account.clone(),
-            token_list_account.clone(),
-            calculate_fee_result_account.clone(),
-        ],
+            &[bridge_account.clone(), token_list_account.clone()],
+            fee_schedule_accounts.as_slice(),
+        ]
+        .concat(),
         _token_index,
-        _amount,
     )?;
-    let calculate_fee_result_data =
-        CalcuateFeeResult::unpack_from_slice(&calculate_fee_result_account.data.borrow())?;
-    let fee = calculate_fee_result_data.fee;
+    // update_fees may have activated a new fee
+    token_list_data = TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    // tokens with a lamport fee pay it into the FeeVault and bridge the whole amount
+    let fee = if token_list_data.lamport_fee > 0 {
+        let (fee_vault_pda, _) = FeeVault::generate_pda_key(_program_id);
+        if !fee_vault_account.key.eq(&fee_vault_pda) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        verify_program_accounts_ownership(_program_id, std::slice::from_ref(fee_vault_account))?;
+        let mut fee_vault_data = FeeVault::unpack_from_slice(&fee_vault_account.data.borrow())?;
+        fee_vault_data.total_fees_collected = fee_vault_data
+            .total_fees_collected
+            .checked_add(token_list_data.lamport_fee)
+            .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+
+        let charge_ix = system_instruction::transfer(
+            source_auth_account.key,
+            fee_vault_account.key,
+            token_list_data.lamport_fee,
+        );
+        program::invoke(
+            &charge_ix,
+            &[
+                source_auth_account.clone(),
+                fee_vault_account.clone(),
+                system_program_account.clone(),
+            ],
+        )?;
+        fee_vault_data.pack_into_slice(&mut fee_vault_account.data.borrow_mut()[..]);
+
+        BridgeEvent::LamportFeeCharged {
+            token_index: _token_index,
+            from: *source_auth_account.key,
+            lamport_fee: token_list_data.lamport_fee,
+            index: bridge_data.current_index,
+        }
+        .emit();
+        0
+    } else {
+        // get the fee calculated
+        quote_fee(
+            _program_id,
+            &[
+                &[
+                    bridge_account.clone(),
+                    token_list_account.clone(),
+                    chain_registry_account.clone(),
+                ],
+                fee_schedule_accounts.as_slice(),
+            ]
+            .concat(),
+            _token_index,
+            bridged_amount,
+        )?
+    };
+
+    // dust the remote decimals can't represent stays with, or goes back to, the sender
+    let (remote_amount, dust) = token_list_data.to_remote_amount(
+        bridged_amount
+            .checked_sub(fee)
+            .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?,
+    )?;
+    let bridged_amount = bridged_amount - dust;
+    if dust > 0 && !token_list_data.mint_burn {
+        let (bridge_pda, bridge_bump, bridge_seed1, bridge_seed2) =
+            Bridge::generate_pda_key(_program_id);
+        let refund_dust_ix = spl_token_2022::instruction::transfer_checked(
+            &token_program,
+            bridge_token_account.key,
+            mint_account.key,
+            source_account.key,
+            &bridge_pda,
+            &[&bridge_pda],
+            dust,
+            decimals,
+        )?;
+        program::invoke_signed(
+            &refund_dust_ix,
+            &[
+                bridge_token_account.clone(),
+                mint_account.clone(),
+                source_account.clone(),
+                bridge_account.clone(),
+            ],
+            &[&[
+                bridge_seed1.as_bytes(),
+                bridge_seed2.as_bytes(),
+                &[bridge_bump],
+            ]],
+        )?;
+    }
+
+    // mint/burn tokens burn the bridged amount, only the fee is kept by the Bridge. A transfer
+    // fee charged by the mint comes off the locked fee, so only what arrives is collected and
+    // recorded as part of the request
+    let (bridged_amount, fee) = if token_list_data.mint_burn {
+        let burn_amount = bridged_amount
+            .checked_sub(fee)
+            .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+        let burn_ix = spl_token_2022::instruction::burn(
+            &token_program,
+            source_account.key,
+            mint_account.key,
+            source_auth_account.key,
+            &[source_auth_account.key],
+            burn_amount,
+        )?;
+        program::invoke(
+            &burn_ix,
+            &[
+                source_account.clone(),
+                mint_account.clone(),
+                source_auth_account.clone(),
+            ],
+        )?;
+        let balance_before = unpack_token_account(bridge_token_account)?.amount;
+        lock(fee)?;
+        let fee_received = unpack_token_account(bridge_token_account)?
+            .amount
+            .checked_sub(balance_before)
+            .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+        let withheld = fee
+            .checked_sub(fee_received)
+            .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+        (bridged_amount - withheld, fee_received)
+    } else {
+        (bridged_amount, fee)
+    };
 
     // tokenData.totalFeesCollected = tokenData.totalFeesCollected.add(_fee);
     token_list_data.total_fees_collected = token_list_data
@@ -467,25 +941,65 @@ fn transfer_request(
         .checked_add(fee)
         .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))
         .unwrap();
-    let transfer_from_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
-        source_account.key,
-        bridge_token_account.key,
-        source_auth_account.key,
-        &[&source_auth_account.key],
-        _amount,
-    )?;
 
-    // source auth account can also be the delegate
-    program::invoke(
-        &transfer_from_ix,
+    // keep an on-chain record of the request at the current index
+    let index = bridge_data.current_index;
+    let (record_pda, record_bump) = TransferRequestRecord::generate_pda_key(_program_id, index);
+    if !transfer_request_record_account.key.eq(&record_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent = Rent::get()?;
+    let create_record_ix = system_instruction::create_account(
+        source_auth_account.key,
+        transfer_request_record_account.key,
+        rent.minimum_balance(TransferRequestRecord::LEN),
+        TransferRequestRecord::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &create_record_ix,
         &[
-            source_account.clone(),
-            mint_account.clone(),
-            bridge_token_account.clone(),
             source_auth_account.clone(),
+            transfer_request_record_account.clone(),
+            system_program_account.clone(),
         ],
+        &[&[
+            index.to_le_bytes().as_ref(),
+            COMMON_BASE_SEED.as_bytes(),
+            TransferRequestRecord::BASE_SEED.as_bytes(),
+            &[record_bump],
+        ]],
     )?;
+
+    let clock = Clock::get()?;
+    let transfer_request_record_data = TransferRequestRecord {
+        is_initialized: true,
+        token_index: _token_index,
+        sender: *source_auth_account.key,
+        mint: *mint_account.key,
+        to: _to,
+        amount: bridged_amount,
+        fee,
+        chain_id: _chain_id,
+        timestamp: clock.unix_timestamp as u64,
+        refunded: false,
+    };
+    transfer_request_record_data
+        .pack_into_slice(&mut transfer_request_record_account.data.borrow_mut()[..]);
+
+    BridgeEvent::TransferRequested {
+        token_index: _token_index,
+        from: *source_auth_account.key,
+        to: _to,
+        amount: bridged_amount,
+        fee,
+        chain_id: _chain_id,
+        index,
+        remote_amount,
+        remote_token,
+    }
+    .emit();
     bridge_data.current_index += 1;
 
     token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
@@ -496,49 +1010,43 @@ fn transfer_request(
 fn transfer_receipt(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _token_index: u64,
-    _from: &Pubkey,
-    _to: &Pubkey,
-    _amount: u64,
-    _chain_id: u64,
-    _index: u64,
-    _signature_account: &Pubkey,
+    _receipt: &Receipt,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(_program_id, _accounts[1..5].as_ref())?;
+    verify_program_accounts_ownership(_program_id, _accounts[0..6].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let destination_auth = next_account_info(account_info_iter)?; // The account the submitting and paying for the transaction
     let bridge_account = next_account_info(account_info_iter)?; // PDA Account
+    let validator_set_account = next_account_info(account_info_iter)?; // PDA Account
+    let chain_registry_account = next_account_info(account_info_iter)?; // PDA Account
     let claimed_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
     let daily_token_claims_account = next_account_info(account_info_iter)?;
-    let source_auth = next_account_info(account_info_iter)?; // The account the transaction creator signed this transaction with - the Aithority of source token
-    let source_token_account = next_account_info(account_info_iter)?;
-    let destination_token_account = next_account_info(account_info_iter)?;
-    let mint_account = next_account_info(account_info_iter)?;
-    let token_program_account = next_account_info(account_info_iter)?;
+    let recipient_claims_account = next_account_info(account_info_iter)?; // PDA Account
+    let pending_claim_account = next_account_info(account_info_iter)?; // PDA Account
+    let instructions_sysvar_account = next_account_info(account_info_iter)?;
 
-    if !destination_auth.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    when_not_paused(&bridge_data)?;
 
-    if !destination_auth.key.eq(_to) {
-        return Err(ProgramError::InvalidArgument);
+    let (claimed_pda, _) =
+        ClaimedDictionary::generate_pda_key(_program_id, _receipt.chain_id, _receipt.index);
+    if !claimed_account.key.eq(&claimed_pda) {
+        return Err(ProgramError::InvalidSeeds);
     }
 
-    let (bridge_pda, _, _, _) = Bridge::generate_pda_key(_program_id);
-    if !bridge_account.key.eq(&bridge_pda) {
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _receipt.token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
         return Err(ProgramError::InvalidSeeds);
     }
 
-    if !source_auth.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-    if *source_auth.key != *_signature_account {
-        return Err(ProgramError::InvalidArgument);
-    }
+    let validator_set_data = unpack_validator_set(_program_id, validator_set_account)?;
+
+    let message = transfer_receipt_message(_program_id, bridge_data.chain_id, _receipt);
+    check_validator_signatures(&validator_set_data, instructions_sysvar_account, &message)?;
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
     let token_data = TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    // '_receipt.amount' is in the remote token's decimals, limits and payouts use the mint's
+    let amount = token_data.to_local_amount(_receipt.amount)?;
     let mut claimed_data = ClaimedDictionary::unpack_from_slice(*claimed_account.data.borrow())?;
     let mut daily_token_claims_data =
         DailyTokenClaimsDictionary::unpack_from_slice(&daily_token_claims_account.data.borrow())?;
@@ -549,85 +1057,392 @@ fn transfer_receipt(
     if token_data.paused {
         return Err(ProgramError::Custom(BridgeError::TokenAlreadyPaused as u32));
     }
-    if bridge_data.chain_id == _chain_id {
+    if bridge_data.chain_id == _receipt.chain_id {
         return Err(ProgramError::Custom(BridgeError::RequestToSameChain as u32));
     }
+    unpack_chain_registry(_program_id, chain_registry_account, _receipt.chain_id)?;
 
     if claimed_data.claimed {
         return Err(ProgramError::Custom(BridgeError::AlreadyClaimed as u32));
     }
-    if token_data.limit > 0 {
-        _update_daily_limit(
+    let (dtc_pda, _) =
+        DailyTokenClaimsDictionary::generate_pda_key(_program_id, _receipt.token_index);
+    if !daily_token_claims_account.key.eq(&dtc_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    daily_token_claims_data
+        .claims
+        .roll(current_timestamp, token_data.limit_window);
+    if token_data.limit > 0
+        && daily_token_claims_data
+            .claims
+            .exceeds(amount, token_data.limit)
+    {
+        return Err(ProgramError::Custom(
+            BridgeError::ClaimAboveDailyLimit as u32,
+        ));
+    }
+
+    // only tokens with a recipient limit track claims per recipient
+    let recipient_claims_data = if token_data.recipient_limit > 0 {
+        let (recipient_claims_pda, _) = RecipientClaimsDictionary::generate_pda_key(
             _program_id,
-            &[
-                token_list_account.clone(),
-                daily_token_claims_account.clone(),
-            ],
-            _index,
+            _receipt.token_index,
+            &_receipt.to,
+        );
+        if !recipient_claims_account.key.eq(&recipient_claims_pda) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        verify_program_accounts_ownership(
+            _program_id,
+            std::slice::from_ref(recipient_claims_account),
         )?;
-        if daily_token_claims_data.daily_token_claims + _amount <= token_data.limit {
+        let mut recipient_claims_data =
+            RecipientClaimsDictionary::unpack_from_slice(&recipient_claims_account.data.borrow())?;
+        if !recipient_claims_data.is_initialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        if recipient_claims_data.window != token_data.limit_window {
+            recipient_claims_data.claims.set_window(
+                current_timestamp,
+                recipient_claims_data.window,
+                token_data.limit_window,
+            )?;
+            recipient_claims_data.window = token_data.limit_window;
+        }
+        recipient_claims_data
+            .claims
+            .roll(current_timestamp, recipient_claims_data.window);
+        if recipient_claims_data
+            .claims
+            .exceeds(amount, token_data.recipient_limit)
+        {
             return Err(ProgramError::Custom(
-                BridgeError::ClaimAboveDailyLimit as u32,
+                BridgeError::ClaimAboveRecipientLimit as u32,
             ));
         }
-    }
+        recipient_claims_data.claims.record(amount)?;
+        Some(recipient_claims_data)
+    } else {
+        None
+    };
 
-    if token_data.token_address != *mint_account.key {
-        return Err(ProgramError::InvalidAccountData);
+    claimed_data.claimed = true;
+    daily_token_claims_data.claims.record(amount)?;
+
+    claimed_data.pack_into_slice(&mut claimed_account.data.borrow_mut()[..]);
+    daily_token_claims_data.pack_into_slice(&mut daily_token_claims_account.data.borrow_mut()[..]);
+    if let Some(recipient_claims_data) = recipient_claims_data {
+        recipient_claims_data.pack_into_slice(&mut recipient_claims_account.data.borrow_mut()[..]);
     }
 
-    let transfer_ix = spl_token::instruction::transfer(
-        &spl_token::id(),
-        source_token_account.key,
-        destination_token_account.key,
-        source_auth.key,
-        &[&source_auth.key],
-        _amount,
-    )?;
+    // large receipts wait in a PendingClaim until ExecutePendingClaim releases them
+    if token_data.settlement_threshold > 0 && amount >= token_data.settlement_threshold {
+        let (pending_claim_pda, _) =
+            PendingClaim::generate_pda_key(_program_id, _receipt.chain_id, _receipt.index);
+        if !pending_claim_account.key.eq(&pending_claim_pda) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        verify_program_accounts_ownership(
+            _program_id,
+            std::slice::from_ref(pending_claim_account),
+        )?;
+        let release_time = current_timestamp
+            .checked_add(token_data.settlement_delay)
+            .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+        let pending_claim_data = PendingClaim {
+            is_initialized: true,
+            token_index: _receipt.token_index,
+            from: _receipt.from,
+            to: _receipt.to,
+            amount,
+            release_time,
+            executed: false,
+            cancelled: false,
+        };
+        pending_claim_data.pack_into_slice(&mut pending_claim_account.data.borrow_mut()[..]);
+
+        BridgeEvent::PendingClaimQueued {
+            token_index: _receipt.token_index,
+            from: _receipt.from,
+            to: _receipt.to,
+            amount,
+            chain_id: _receipt.chain_id,
+            index: _receipt.index,
+            release_time,
+        }
+        .emit();
+        return Ok(());
+    }
 
-    program::invoke(
-        &transfer_ix,
-        &[
-            source_token_account.clone(),
-            destination_token_account.clone(),
-            source_auth.clone(),
-            token_program_account.clone(),
-        ],
+    release_claim(
+        _program_id,
+        bridge_account,
+        &token_data,
+        &_accounts[9..],
+        &_receipt.to,
+        amount,
     )?;
 
-    claimed_data.claimed = true;
-    daily_token_claims_data.daily_token_claims += _amount;
-
-    claimed_data.pack_into_slice(&mut &mut claimed_account.data.borrow_mut()[..]);
-    daily_token_claims_data
-        .pack_into_slice(&mut &mut daily_token_claims_account.data.borrow_mut()[..]);
-
+    BridgeEvent::TransferReceived {
+        token_index: _receipt.token_index,
+        from: _receipt.from,
+        to: _receipt.to,
+        amount,
+        chain_id: _receipt.chain_id,
+        index: _receipt.index,
+    }
+    .emit();
     Ok(())
 }
 
-fn update_verify_address(
+fn refund_request(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _verify_address: &Pubkey,
+    _index: u64,
+    _refund_fee: bool,
 ) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[0..4].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let bridge_account = next_account_info(account_info_iter)?;
-
-    if !owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    let bridge_account = next_account_info(account_info_iter)?; // PDA Account
+    let validator_set_account = next_account_info(account_info_iter)?; // PDA Account
+    let transfer_request_record_account = next_account_info(account_info_iter)?; // PDA Account
+    let token_list_account = next_account_info(account_info_iter)?;
+    let instructions_sysvar_account = next_account_info(account_info_iter)?;
+    let bridge_token_account = next_account_info(account_info_iter)?; // PDA token account
+    let sender_token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_program_account = next_account_info(account_info_iter)?;
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    when_not_paused(&bridge_data)?;
+    let (bridge_pda, bridge_bump, bridge_seed1, bridge_seed2) =
+        Bridge::generate_pda_key(_program_id);
+
+    // the rejection must be attested by 'threshold' distinct validators
+    let validator_set_data = unpack_validator_set(_program_id, validator_set_account)?;
+    let message = refund_request_message(_program_id, bridge_data.chain_id, _index, _refund_fee);
+    check_validator_signatures(&validator_set_data, instructions_sysvar_account, &message)?;
+
+    let (record_pda, _) = TransferRequestRecord::generate_pda_key(_program_id, _index);
+    if !transfer_request_record_account.key.eq(&record_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut record_data =
+        TransferRequestRecord::unpack_from_slice(&transfer_request_record_account.data.borrow())?;
+    if !record_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if record_data.refunded {
+        return Err(ProgramError::Custom(BridgeError::AlreadyRefunded as u32));
     }
 
-    if !bridge_account.owner.eq(_program_id) {
-        return Err(ProgramError::IllegalOwner);
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, record_data.token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut token_data = TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    if record_data.mint != *mint_account.key {
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    let mut bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
+    // the fee was added to the collected fees when the request was made, and stays there
+    // until the next withdrawal takes the collected fees to the treasury
+    let fee_refunded = if _refund_fee {
+        record_data.refundable_fee(_index, token_data.fees_withdrawn_index)
+    } else {
+        0
+    };
+    token_data.total_fees_collected = token_data
+        .total_fees_collected
+        .checked_sub(fee_refunded)
+        .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+    let bridged_amount = record_data
+        .amount
+        .checked_sub(record_data.fee)
+        .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+    let refunded_amount = bridged_amount
+        .checked_add(fee_refunded)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+
+    if token_data.mint_burn {
+        // only the fee was locked, the rest was burned and is minted back
+        release_claim(
+            _program_id,
+            bridge_account,
+            &token_data,
+            &_accounts[5..],
+            &record_data.sender,
+            bridged_amount,
+        )?;
+        if fee_refunded > 0 {
+            let transfer_ix = spl_token_2022::instruction::transfer_checked(
+                &token_data.token_program,
+                bridge_token_account.key,
+                mint_account.key,
+                sender_token_account.key,
+                &bridge_pda,
+                &[&bridge_pda],
+                fee_refunded,
+                mint_decimals(mint_account)?,
+            )?;
+            program::invoke_signed(
+                &transfer_ix,
+                &[
+                    bridge_token_account.clone(),
+                    mint_account.clone(),
+                    sender_token_account.clone(),
+                    bridge_account.clone(),
+                    token_program_account.clone(),
+                ],
+                &[&[
+                    bridge_seed1.as_bytes(),
+                    bridge_seed2.as_bytes(),
+                    &[bridge_bump],
+                ]],
+            )?;
+        }
+    } else {
+        release_claim(
+            _program_id,
+            bridge_account,
+            &token_data,
+            &_accounts[5..],
+            &record_data.sender,
+            refunded_amount,
+        )?;
+    }
 
-    only_owner(&owner_account, &bridge_data)?;
+    record_data.refunded = true;
+    record_data.pack_into_slice(&mut transfer_request_record_account.data.borrow_mut()[..]);
+    token_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
 
-    bridge_data.verify_address = *_verify_address;
-    bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::TransferRefunded {
+        token_index: record_data.token_index,
+        to: record_data.sender,
+        amount: refunded_amount,
+        fee_refunded,
+        index: _index,
+    }
+    .emit();
+    Ok(())
+}
+
+// Pays 'amount' of a claimed receipt out to 'to', minting it for mint/burn tokens.
+// 'token_accounts' are the Bridge token, destination token, mint and SPL Token program
+// accounts, followed by the WPokt state and w_pokt program accounts for mint/burn tokens.
+fn release_claim<'a>(
+    _program_id: &Pubkey,
+    bridge_account: &AccountInfo<'a>,
+    token_data: &TokenListDictionary,
+    token_accounts: &[AccountInfo<'a>],
+    to: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut token_accounts.iter();
+    let bridge_token_account = next_account_info(account_info_iter)?; // PDA token account
+    let destination_token_account = next_account_info(account_info_iter)?;
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_program_account = next_account_info(account_info_iter)?;
+
+    let (bridge_pda, bridge_bump, bridge_seed1, bridge_seed2) =
+        Bridge::generate_pda_key(_program_id);
+
+    if token_data.token_address != *mint_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(_program_id, mint_account.key);
+    if !bridge_token_account.key.eq(&bridge_token_pda) {
+        return Err(ProgramError::Custom(
+            BridgeError::TokenAccountKeyMismatch as u32,
+        ));
+    }
+
+    if token_data.token_program != *token_program_account.key {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let destination_token_data = unpack_token_account(destination_token_account)?;
+    if destination_token_data.owner != *to {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if token_data.mint_burn {
+        let wpokt_account = next_account_info(account_info_iter)?;
+        let w_pokt_program_account = next_account_info(account_info_iter)?;
+
+        // the mint authority pins the WPokt state account, whose owner pins the w_pokt program
+        if mint_account.owner != &spl_token::id() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mint_data = spl_token::state::Mint::unpack(&mint_account.data.borrow())?;
+        if mint_data.mint_authority != COption::Some(*wpokt_account.key)
+            || wpokt_account.owner != w_pokt_program_account.key
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mint_ix = Instruction::new_with_bytes(
+            *w_pokt_program_account.key,
+            &WPoktInstruction::MintOnlyBridge { amount }
+                .try_to_vec()
+                .map_err(|_| ProgramError::InvalidInstructionData)?,
+            vec![
+                AccountMeta::new_readonly(*wpokt_account.key, false),
+                AccountMeta::new_readonly(bridge_pda, true),
+                AccountMeta::new(*mint_account.key, false),
+                AccountMeta::new(*destination_token_account.key, false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ],
+        );
+
+        program::invoke_signed(
+            &mint_ix,
+            &[
+                wpokt_account.clone(),
+                bridge_account.clone(),
+                mint_account.clone(),
+                destination_token_account.clone(),
+                token_program_account.clone(),
+                w_pokt_program_account.clone(),
+            ],
+            &[&[
+                bridge_seed1.as_bytes(),
+                bridge_seed2.as_bytes(),
+                &[bridge_bump],
+            ]],
+        )?;
+    } else {
+        let transfer_ix = spl_token_2022::instruction::transfer_checked(
+            &token_data.token_program,
+            bridge_token_account.key,
+            mint_account.key,
+            destination_token_account.key,
+            &bridge_pda,
+            &[&bridge_pda],
+            amount,
+            mint_decimals(mint_account)?,
+        )?;
+
+        program::invoke_signed(
+            &transfer_ix,
+            &[
+                bridge_token_account.clone(),
+                mint_account.clone(),
+                destination_token_account.clone(),
+                bridge_account.clone(),
+                token_program_account.clone(),
+            ],
+            &[&[
+                bridge_seed1.as_bytes(),
+                bridge_seed2.as_bytes(),
+                &[bridge_bump],
+            ]],
+        )?;
+    }
     Ok(())
 }
 
@@ -639,20 +1454,19 @@ fn update_token_limit(
 ) -> ProgramResult {
     verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
+    unpack_bridge(_program_id, bridge_account)?;
+
+    if !executor_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    only_owner(&owner_account, &bridge_data)?;
-
     let (token_list_pda, _, _, _) =
         TokenListDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
@@ -661,749 +1475,5465 @@ fn update_token_limit(
 
     token_list_data.limit = _limit;
     token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::TokenLimitUpdated {
+        token_index: _token_index,
+        limit: _limit,
+    }
+    .emit();
     Ok(())
 }
 
-fn set_token_limit_time(
+fn update_recipient_limit(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _token_index: u64,
-    _timestamp: u64,
+    _limit: u64,
 ) -> ProgramResult {
     verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
+    unpack_bridge(_program_id, bridge_account)?;
+
+    if !executor_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    only_owner(&owner_account, &bridge_data)?;
-
     let (token_list_pda, _, _, _) =
         TokenListDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
         return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
 
-    token_list_data.limit_timestamp = _timestamp;
-    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    token_list_data.recipient_limit = _limit;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::RecipientLimitUpdated {
+        token_index: _token_index,
+        limit: _limit,
+    }
+    .emit();
     Ok(())
 }
 
-fn update_stable_fee(
+fn set_settlement(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _new_stable_fee: u64,
+    _token_index: u64,
+    _threshold: u64,
+    _delay: u64,
 ) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    unpack_bridge(_program_id, bridge_account)?;
 
-    if !bridge_account.owner.eq(_program_id) {
-        return Err(ProgramError::IllegalOwner);
+    if !executor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let mut bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-    only_owner(&owner_account, &bridge_data)?;
-
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
-
-    let sum_result = bridge_data
-        .fee_update_duration
-        .checked_add(current_timestamp as u64);
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    match sum_result {
-        None => return Err(ProgramError::Custom(BridgeError::Overflow as u32)),
-        Some(new_stable_fee_update_time) => {
-            bridge_data.new_stable_fee = _new_stable_fee;
-            bridge_data.stable_fee_update_time = new_stable_fee_update_time;
-        }
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
 
-    bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
+    token_list_data.settlement_threshold = _threshold;
+    token_list_data.settlement_delay = _delay;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::SettlementUpdated {
+        token_index: _token_index,
+        threshold: _threshold,
+        delay: _delay,
+    }
+    .emit();
     Ok(())
 }
 
-fn update_token_fee(
+fn set_remote_decimals(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _index: u64,
-    _new_token_fee: u64,
+    _token_index: u64,
+    _remote_decimals: u8,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(_program_id, _accounts[1..].as_ref())?;
-
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    unpack_bridge(_program_id, bridge_account)?;
 
-    if !owner_account.is_signer {
+    if !executor_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    only_owner(&owner_account, &bridge_data)?;
-
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
-
-    // bridge_data.stable_fee_update_time
-    let sum_result = bridge_data
-        .fee_update_duration
-        .checked_add(current_timestamp as u64);
-
-    let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(_program_id, _index);
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
         return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
 
-    match sum_result {
-        None => return Err(ProgramError::Custom(BridgeError::Overflow as u32)),
-        Some(new_fee_update_time) => {
-            token_list_data.fee_update_time = new_fee_update_time;
-            token_list_data.new_fee = _new_token_fee;
-            token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
-        }
-    }
+    // amounts must be scalable both ways
+    decimals_factor(_remote_decimals.abs_diff(token_list_data.local_decimals))?;
 
+    token_list_data.remote_decimals = _remote_decimals;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::RemoteDecimalsUpdated {
+        token_index: _token_index,
+        local_decimals: token_list_data.local_decimals,
+        remote_decimals: _remote_decimals,
+    }
+    .emit();
     Ok(())
 }
 
-fn update_fees(
+fn set_lamport_fee(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _token_index: u64,
+    _lamport_fee: u64,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(_program_id, _accounts)?;
-    _update_stable_fee(_program_id, _accounts)?;
-    _update_token_fee(_program_id, _accounts, _token_index)?;
-    Ok(())
-}
-
-fn withdraw_fees(_program_id: &Pubkey, _accounts: &[AccountInfo], _index: u64) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let owner_token_account = next_account_info(account_info_iter)?;
-    let bridge_account = next_account_info(account_info_iter)?; // the PDA account
-    let bridge_token_account = next_account_info(account_info_iter)?; // PDA token account
-    let mint_account = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
+    let fee_vault_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
 
-    let (pda, bump, seed1, seed2) = Bridge::generate_pda_key(_program_id);
-
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-
-    only_owner(owner_account, &bridge_data)?;
+    unpack_bridge(_program_id, bridge_account)?;
 
-    let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(_program_id, _index);
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
         return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
-
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    if !token_list_data.exists {
+        return Err(ProgramError::Custom(BridgeError::NonExistantToken as u32));
+    }
 
-    if !(token_list_data.total_fees_collected > 0) {
-        return Err(ProgramError::Custom(BridgeError::NothingToWithdraw as u32));
+    // the vault is shared by all tokens, the first lamport fee set creates it
+    let (fee_vault_pda, fee_vault_bump) = FeeVault::generate_pda_key(_program_id);
+    if !fee_vault_account.key.eq(&fee_vault_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if fee_vault_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            executor_account.key,
+            fee_vault_account.key,
+            rent_sysvar.minimum_balance(FeeVault::LEN),
+            FeeVault::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                executor_account.clone(),
+                fee_vault_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                COMMON_BASE_SEED.as_bytes(),
+                FeeVault::BASE_SEED.as_bytes(),
+                &[fee_vault_bump],
+            ]],
+        )?;
+        let fee_vault_data = FeeVault {
+            is_initialized: true,
+            total_fees_collected: 0,
+        };
+        fee_vault_data.pack_into_slice(&mut fee_vault_account.data.borrow_mut()[..]);
+    }
+
+    token_list_data.lamport_fee = _lamport_fee;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::LamportFeeUpdated {
+        token_index: _token_index,
+        lamport_fee: _lamport_fee,
     }
+    .emit();
+    Ok(())
+}
+
+fn set_fee_rounding(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _rounding: RoundingMode,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
 
-    let to_transfer = token_list_data.total_fees_collected;
-    token_list_data.total_fees_collected = 0;
+    unpack_bridge(_program_id, bridge_account)?;
 
-    if token_list_data.token_address != *mint_account.key {
-        return Err(ProgramError::InvalidAccountData);
+    if !executor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let pda_seeds: &[&[u8]] = &[seed1.as_bytes(), seed2.as_bytes(), &[bump]];
-    let mint_data = spl_token::state::Mint::unpack_from_slice(&mint_account.data.borrow())?;
-    let transfer_ix = spl_token::instruction::transfer_checked(
-        &spl_token::id(),
-        bridge_token_account.key,
-        mint_account.key,
-        owner_token_account.key,
-        &pda,
-        &[&pda],
-        to_transfer,
-        mint_data.decimals,
-    )?;
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    program::invoke_signed(
-        &transfer_ix,
-        &[
-            bridge_token_account.clone(),
-            mint_account.clone(),
-            owner_token_account.clone(),
-            bridge_account.clone(),
-        ],
-        &[pda_seeds],
-    )?;
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
+    }
 
-    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    token_list_data.fee_rounding = _rounding;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::FeeRoundingUpdated {
+        token_index: _token_index,
+        rounding: _rounding,
+    }
+    .emit();
     Ok(())
 }
 
-fn add_token(
+fn set_remote_token(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _index: u64,
-    _token_address: Pubkey,
-    _fee: u64,
-    _limit: u64,
+    _token_index: u64,
+    _chain_id: u64,
+    _remote_token: Vec<u8>,
 ) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let bridge_account = next_account_info(account_info_iter)?; // PDA Account
-    let token_list_account = next_account_info(account_info_iter)?; // PDA Account
-    let token_added_account = next_account_info(account_info_iter)?; // PDA Account
-    let mint = next_account_info(account_info_iter)?;
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+    let remote_token_account = next_account_info(account_info_iter)?;
     let rent_sysvar_account = next_account_info(account_info_iter)?;
     let system_program_account = next_account_info(account_info_iter)?;
 
-    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    unpack_bridge(_program_id, bridge_account)?;
 
-    if !owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+    if _remote_token.len() > MAX_REMOTE_TOKEN_LEN {
+        return Err(ProgramError::Custom(BridgeError::RemoteTokenTooLong as u32));
     }
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-    only_owner(&owner_account, &bridge_data)?;
-
-    // create and initialize TokenAdded dictionary item account
-    let (token_added_pda, token_added_bump, token_added_seed1, token_added_seed2) =
-        TokenAddedDictionary::generate_pda_key(_program_id, mint.key);
-    if !token_added_account.key.eq(&token_added_pda) {
-        return Err(ProgramError::InvalidSeeds);
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
+    }
+    let token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    if !token_list_data.exists {
+        return Err(ProgramError::Custom(BridgeError::NonExistantToken as u32));
     }
 
-    let create_token_added_pda_ix = system_instruction::create_account(
-        owner_account.key,
-        token_added_account.key,
-        rent_sysvar.minimum_balance(TokenAddedDictionary::LEN),
-        TokenAddedDictionary::LEN.try_into().unwrap(),
-        _program_id,
-    );
-    program::invoke_signed(
-        &create_token_added_pda_ix,
-        &[
-            owner_account.clone(),
-            token_added_account.clone(),
-            system_program_account.clone(),
-        ],
-        &[&[
-            _token_address.as_ref(),
-            token_added_seed1.as_ref(),
-            token_added_seed2.as_ref(),
-            &[token_added_bump],
-        ]],
-    )?;
-
-    let mut token_added_data =
-        TokenAddedDictionary::unpack_from_slice(&token_added_account.data.borrow())?;
-    token_added_data.token_added = true;
-    token_added_data.pack_into_slice(&mut &mut token_added_account.data.borrow_mut()[..]);
-
-    // // create and initialize TokenList dictionary item account
-    let (token_list_pda, token_list_bump, token_list_seed1, token_list_seed2) =
-        TokenListDictionary::generate_pda_key(_program_id, _index);
-
-    if !token_list_account.key.eq(&token_list_pda) {
+    let (pda, bump) = RemoteTokenMapping::generate_pda_key(_program_id, _token_index, _chain_id);
+    if !remote_token_account.key.eq(&pda) {
         return Err(ProgramError::InvalidSeeds);
     }
 
-    let create_token_list_pda_ix = system_instruction::create_account(
-        owner_account.key,
-        token_list_account.key,
-        rent_sysvar.minimum_balance(TokenListDictionary::LEN),
-        TokenListDictionary::LEN.try_into().unwrap(),
-        _program_id,
-    );
-    program::invoke_signed(
-        &create_token_list_pda_ix,
-        &[
-            owner_account.clone(),
-            token_list_account.clone(),
-            system_program_account.clone(),
-        ],
-        &[&[
-            _index.to_le_bytes().as_ref(),
-            token_list_seed1.as_ref(),
-            token_list_seed2.as_ref(),
-            &[token_list_bump],
-        ]],
-    )?;
-
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
-    let limit_timestamp = SECONDS_PER_DAY
-        .checked_add(current_timestamp as u64)
-        .unwrap();
-
-    let token_data_list = TokenListDictionary {
+    if remote_token_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            executor_account.key,
+            remote_token_account.key,
+            rent_sysvar.minimum_balance(RemoteTokenMapping::LEN),
+            RemoteTokenMapping::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                executor_account.clone(),
+                remote_token_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                _token_index.to_le_bytes().as_ref(),
+                _chain_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                RemoteTokenMapping::BASE_SEED.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+    }
+    let remote_token_data = RemoteTokenMapping {
         is_initialized: true,
-        token_address: _token_address,
-        exists: true,
-        paused: false,
-        // total fees collected
-        total_fees_collected: 0,
-        // current fee
-        fee: 0,
-        // fee update time
-        fee_update_time: 0,
-        // new fee
-        new_fee: 0,
-        // daily limit
-        limit: 0,
-        // daily limit time
-        limit_timestamp: limit_timestamp,
+        remote_token: _remote_token.clone(),
     };
-    token_data_list.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    remote_token_data.pack_into_slice(&mut remote_token_account.data.borrow_mut()[..]);
+
+    BridgeEvent::RemoteTokenUpdated {
+        token_index: _token_index,
+        chain_id: _chain_id,
+        remote_token: _remote_token,
+    }
+    .emit();
     Ok(())
 }
 
-fn pause_token(
+fn execute_pending_claim(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _token_index: u64,
+    _chain_id: u64,
+    _index: u64,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(&_program_id, _accounts[1..].as_ref())?;
-
+    verify_program_accounts_ownership(_program_id, _accounts[0..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let bridge_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?; // PDA Account
+    let pending_claim_account = next_account_info(account_info_iter)?; // PDA Account
     let token_list_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-    let mut token_list_data =
-        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    when_not_paused(&bridge_data)?;
 
-    only_owner(&owner_account, &bridge_data)?;
+    let mut pending_claim_data =
+        unpack_pending_claim(_program_id, pending_claim_account, _chain_id, _index)?;
+    if (Clock::get()?.unix_timestamp as u64) < pending_claim_data.release_time {
+        return Err(ProgramError::Custom(
+            BridgeError::PendingClaimNotReady as u32,
+        ));
+    }
 
     let (token_list_pda, _, _, _) =
-        TokenListDictionary::generate_pda_key(_program_id, _token_index);
-
+        TokenListDictionary::generate_pda_key(_program_id, pending_claim_data.token_index);
     if !token_list_account.key.eq(&token_list_pda) {
         return Err(ProgramError::InvalidSeeds);
     }
-
-    if token_list_data.paused == true {
+    let token_data = TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    if token_data.paused {
         return Err(ProgramError::Custom(BridgeError::TokenAlreadyPaused as u32));
     }
 
-    token_list_data.paused = true;
-    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    pending_claim_data.executed = true;
+    pending_claim_data.pack_into_slice(&mut pending_claim_account.data.borrow_mut()[..]);
+
+    release_claim(
+        _program_id,
+        bridge_account,
+        &token_data,
+        &_accounts[3..],
+        &pending_claim_data.to,
+        pending_claim_data.amount,
+    )?;
+
+    BridgeEvent::TransferReceived {
+        token_index: pending_claim_data.token_index,
+        from: pending_claim_data.from,
+        to: pending_claim_data.to,
+        amount: pending_claim_data.amount,
+        chain_id: _chain_id,
+        index: _index,
+    }
+    .emit();
     Ok(())
 }
 
-fn unpause_token(
+fn cancel_pending_claim(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _token_index: u64,
+    _chain_id: u64,
+    _index: u64,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(&_program_id, _accounts[1..].as_ref())?;
-
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
+    let caller_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
-    let token_list_account = next_account_info(account_info_iter)?;
+    let pending_claim_account = next_account_info(account_info_iter)?;
 
-    if !owner_account.is_signer {
+    if !caller_account.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-    let mut token_list_data =
-        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
-
-    only_owner(&owner_account, &bridge_data)?;
-
-    let (token_list_pda, _, _, _) =
-        TokenListDictionary::generate_pda_key(_program_id, _token_index);
-
-    if !token_list_account.key.eq(&token_list_pda) {
-        return Err(ProgramError::InvalidSeeds);
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    if bridge_data.guardian != *caller_account.key && bridge_data.owner != *caller_account.key {
+        return Err(ProgramError::IllegalOwner);
     }
 
-    if token_list_data.paused == false {
+    let mut pending_claim_data =
+        unpack_pending_claim(_program_id, pending_claim_account, _chain_id, _index)?;
+    if (Clock::get()?.unix_timestamp as u64) >= pending_claim_data.release_time {
         return Err(ProgramError::Custom(
-            BridgeError::TokenAlreadyUnaused as u32,
+            BridgeError::PendingClaimReleased as u32,
         ));
     }
 
-    token_list_data.paused = false;
-    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    // the amount stays counted in the token's and the recipient's claim windows until it rolls
+    // out of them
+    pending_claim_data.cancelled = true;
+    pending_claim_data.pack_into_slice(&mut pending_claim_account.data.borrow_mut()[..]);
+
+    BridgeEvent::PendingClaimCancelled {
+        chain_id: _chain_id,
+        index: _index,
+    }
+    .emit();
     Ok(())
 }
 
-fn _update_daily_limit(
+fn set_token_limit_window(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _token_index: u64,
+    _window: u64,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(&_program_id, _accounts.as_ref())?;
-
+    verify_program_accounts_ownership(_program_id, _accounts[1..4].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
     let daily_token_claims_account = next_account_info(account_info_iter)?;
 
+    unpack_bridge(_program_id, bridge_account)?;
+
+    if !executor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !RateLimitWindow::is_valid_window(_window) {
+        return Err(ProgramError::Custom(BridgeError::InvalidLimitWindow as u32));
+    }
+
     let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
-    let mut daily_token_claims_data =
-        DailyTokenClaimsDictionary::unpack_from_slice(&daily_token_claims_account.data.borrow())?;
 
     let (token_list_pda, _, _, _) =
         TokenListDictionary::generate_pda_key(_program_id, _token_index);
-    let (dtc_pda, _) = DailyTokenClaimsDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
-        return Err(ProgramError::InvalidSeeds);
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
+    let (dtc_pda, _) = DailyTokenClaimsDictionary::generate_pda_key(_program_id, _token_index);
     if !daily_token_claims_account.key.eq(&dtc_pda) {
         return Err(ProgramError::InvalidSeeds);
     }
 
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
+    let mut daily_token_claims_data =
+        DailyTokenClaimsDictionary::unpack_from_slice(&daily_token_claims_account.data.borrow())?;
+    daily_token_claims_data.claims.set_window(
+        Clock::get()?.unix_timestamp as u64,
+        token_list_data.limit_window,
+        _window,
+    )?;
 
-    if current_timestamp as u64 <= token_list_data.limit_timestamp {
-        return Ok(());
+    token_list_data.limit_window = _window;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    daily_token_claims_data.pack_into_slice(&mut daily_token_claims_account.data.borrow_mut()[..]);
+    BridgeEvent::TokenLimitWindowUpdated {
+        token_index: _token_index,
+        window: _window,
     }
-    let limit_timestamp = SECONDS_PER_DAY
-        .checked_add(current_timestamp as u64)
-        .unwrap();
-
-    token_list_data.limit_timestamp = limit_timestamp;
-    daily_token_claims_data.daily_token_claims = 0;
-
-    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
-    daily_token_claims_data
-        .pack_into_slice(&mut &mut daily_token_claims_account.data.borrow_mut()[..]);
+    .emit();
     Ok(())
 }
 
-/// Accounts expected
-/// 1. `[writable]` the TokenList account
-fn _update_token_fee(
+fn set_outbound_limit(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _token_index: u64,
+    _limit: u64,
+    _window: u64,
 ) -> ProgramResult {
-    let token_list_account = &_accounts[1];
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+    let outbound_limit_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    if !RateLimitWindow::is_valid_window(_window) {
+        return Err(ProgramError::Custom(BridgeError::InvalidLimitWindow as u32));
+    }
 
     let (token_list_pda, _, _, _) =
         TokenListDictionary::generate_pda_key(_program_id, _token_index);
     if !token_list_account.key.eq(&token_list_pda) {
-        return Err(ProgramError::InvalidSeeds);
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
-
-    let mut token_list_data =
+    let token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
-
-    let clock = Clock::get()?;
-    let current_timestamp = clock.unix_timestamp;
-
-    if token_list_data.fee_update_time == 0 {
-        return Ok(());
+    if !token_list_data.exists {
+        return Err(ProgramError::Custom(BridgeError::NonExistantToken as u32));
     }
 
-    if current_timestamp as u64 > token_list_data.fee_update_time {
-        token_list_data.fee = token_list_data.new_fee;
-        token_list_data.fee_update_time = 0;
-        token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    let (pda, bump) = OutboundLimitDictionary::generate_pda_key(_program_id, _token_index);
+    if !outbound_limit_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
     }
 
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let outbound_limit_data = if outbound_limit_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            executor_account.key,
+            outbound_limit_account.key,
+            rent_sysvar.minimum_balance(OutboundLimitDictionary::LEN),
+            OutboundLimitDictionary::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                executor_account.clone(),
+                outbound_limit_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                _token_index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                OutboundLimitDictionary::BASE_SEED.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+        OutboundLimitDictionary {
+            is_initialized: true,
+            limit: _limit,
+            window: _window,
+            transfers: RateLimitWindow {
+                last_bucket: RateLimitWindow::bucket(current_timestamp, _window),
+                ..Default::default()
+            },
+        }
+    } else {
+        let mut outbound_limit_data =
+            OutboundLimitDictionary::unpack_from_slice(&outbound_limit_account.data.borrow())?;
+        outbound_limit_data.transfers.set_window(
+            current_timestamp,
+            outbound_limit_data.window,
+            _window,
+        )?;
+        outbound_limit_data.limit = _limit;
+        outbound_limit_data.window = _window;
+        outbound_limit_data
+    };
+    outbound_limit_data.pack_into_slice(&mut outbound_limit_account.data.borrow_mut()[..]);
+
+    BridgeEvent::OutboundLimitUpdated {
+        token_index: _token_index,
+        limit: _limit,
+        window: _window,
+    }
+    .emit();
     Ok(())
 }
 
-/// Accounts Expected
-/// 0. `[writable]` the Bridge account
-fn _update_stable_fee(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
-    let bridge_account: &AccountInfo = &_accounts[0];
-    let mut bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
+fn update_stable_fee(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _new_stable_fee: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let fee_manager_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
 
-    if bridge_data.stable_fee_update_time == 0 {
-        return Ok(());
+    if !fee_manager_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
     }
 
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    only_role(
+        _program_id,
+        fee_manager_account,
+        role_account,
+        Role::FeeManager,
+    )?;
+
     let clock = Clock::get()?;
     let current_timestamp = clock.unix_timestamp;
 
-    if current_timestamp as u64 > bridge_data.stable_fee_update_time {
-        bridge_data.stable_fee = bridge_data.new_stable_fee;
-        bridge_data.stable_fee_update_time = 0;
-        bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
+    let sum_result = bridge_data
+        .fee_update_duration
+        .checked_add(current_timestamp as u64);
+
+    match sum_result {
+        None => return Err(ProgramError::Custom(BridgeError::Overflow as u32)),
+        Some(new_stable_fee_update_time) => {
+            bridge_data.new_stable_fee = _new_stable_fee;
+            bridge_data.stable_fee_update_time = new_stable_fee_update_time;
+        }
     }
 
+    bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::StableFeeUpdateScheduled {
+        new_stable_fee: bridge_data.new_stable_fee,
+        stable_fee_update_time: bridge_data.stable_fee_update_time,
+    }
+    .emit();
     Ok(())
 }
 
-fn calculate_fee(
+fn update_token_fee(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _token_index: u64,
-    _amount: u64,
+    _index: u64,
+    _new_token_fee: u64,
 ) -> ProgramResult {
-    verify_program_accounts_ownership(&_program_id, _accounts.as_ref())?;
+    verify_program_accounts_ownership(_program_id, _accounts[1..].as_ref())?;
 
     let account_info_iter = &mut _accounts.iter();
+    let fee_manager_account = next_account_info(account_info_iter)?;
     let bridge_account = next_account_info(account_info_iter)?;
     let token_list_account = next_account_info(account_info_iter)?;
-    let calculate_fee_result_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
 
-    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
-    let mut calculate_fee_result_data =
-        CalcuateFeeResult::unpack_from_slice(&calculate_fee_result_account.data.borrow())?;
+    if !fee_manager_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
 
-    let (token_list_pda, _, _, _) =
-        TokenListDictionary::generate_pda_key(_program_id, _token_index);
-    if !token_list_account.key.eq(&token_list_pda) {
-        return Err(ProgramError::InvalidSeeds);
-    }
-
-    let token_list_data =
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    let mut token_list_data =
         TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
 
-    if token_list_data.fee != 0 {
-        if token_list_data.fee >= TEN_POW_18 {
-            calculate_fee_result_data.fee = 0;
-            calculate_fee_result_data
-                .pack_into_slice(&mut &mut calculate_fee_result_account.data.borrow_mut()[..]);
-
-            return Ok(());
-        }
-        return match _amount.checked_mul(token_list_data.fee) {
-            None => return Err(ProgramError::Custom(BridgeError::Overflow as u32)),
-            Some(product) => {
-                let quotient: u64 = product / TEN_POW_18;
-                calculate_fee_result_data.fee = quotient;
-                calculate_fee_result_data
-                    .pack_into_slice(&mut &mut calculate_fee_result_account.data.borrow_mut()[..]);
+    only_role(
+        _program_id,
+        fee_manager_account,
+        role_account,
+        Role::FeeManager,
+    )?;
 
-                Ok(())
-            }
-        };
-    }
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
 
-    if bridge_data.stable_fee >= TEN_POW_18 {
-        calculate_fee_result_data.fee = 0;
-        calculate_fee_result_data
-            .pack_into_slice(&mut &mut calculate_fee_result_account.data.borrow_mut()[..]);
+    // bridge_data.stable_fee_update_time
+    let sum_result = bridge_data
+        .fee_update_duration
+        .checked_add(current_timestamp as u64);
 
-        return Ok(());
+    let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(_program_id, _index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
 
-    let result: u64 = match _amount.checked_mul(bridge_data.stable_fee) {
+    match sum_result {
         None => return Err(ProgramError::Custom(BridgeError::Overflow as u32)),
-        Some(product) => product / TEN_POW_18,
-    };
-
-    calculate_fee_result_data.fee = result;
-    calculate_fee_result_data
-        .pack_into_slice(&mut &mut calculate_fee_result_account.data.borrow_mut()[..]);
+        Some(new_fee_update_time) => {
+            token_list_data.fee_update_time = new_fee_update_time;
+            token_list_data.new_fee = _new_token_fee;
+            token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+            BridgeEvent::TokenFeeUpdateScheduled {
+                token_index: _index,
+                new_fee: _new_token_fee,
+                fee_update_time: new_fee_update_time,
+            }
+            .emit();
+        }
+    }
 
     Ok(())
 }
 
-fn renounce_ownership(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+fn update_fee_schedule(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _tiers: &[FeeTier],
+    _min_fee: u64,
+    _max_fee: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let wpokt_account = next_account_info(account_info_iter)?;
+    let fee_manager_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+    let fee_schedule_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
 
-    if wpokt_account.owner != _program_id {
-        return Err(ProgramError::IncorrectProgramId);
+    only_role(
+        _program_id,
+        fee_manager_account,
+        role_account,
+        Role::FeeManager,
+    )?;
+    validate_fee_schedule(_tiers, _min_fee, _max_fee)?;
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
-    let mut bridge_data = Bridge::unpack_from_slice(&wpokt_account.data.borrow())?;
-    if !bridge_data.is_initialized {
-        return Err(ProgramError::UninitializedAccount);
+    let token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    if !token_list_data.exists {
+        return Err(ProgramError::Custom(BridgeError::NonExistantToken as u32));
     }
 
-    only_owner(owner_account, &bridge_data)?;
+    let (pda, bump) = FeeSchedule::generate_pda_key(_program_id, _token_index);
+    if !fee_schedule_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut fee_schedule_data = if fee_schedule_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            fee_manager_account.key,
+            fee_schedule_account.key,
+            rent_sysvar.minimum_balance(FeeSchedule::LEN),
+            FeeSchedule::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                fee_manager_account.clone(),
+                fee_schedule_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                _token_index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                FeeSchedule::BASE_SEED.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+        FeeSchedule {
+            is_initialized: true,
+            ..Default::default()
+        }
+    } else {
+        verify_program_accounts_ownership(_program_id, std::slice::from_ref(fee_schedule_account))?;
+        FeeSchedule::unpack_from_slice(&fee_schedule_account.data.borrow())?
+    };
 
-    bridge_data.owner = Pubkey::new_from_array([0_u8; 32]);
-    bridge_data.pack_into_slice(&mut &mut wpokt_account.data.borrow_mut()[..]);
+    let update_time = bridge_data
+        .fee_update_duration
+        .checked_add(Clock::get()?.unix_timestamp as u64)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+    fee_schedule_data.update_time = update_time;
+    fee_schedule_data.new_tiers = _tiers.to_vec();
+    fee_schedule_data.new_min_fee = _min_fee;
+    fee_schedule_data.new_max_fee = _max_fee;
+    fee_schedule_data.pack_into_slice(&mut fee_schedule_account.data.borrow_mut()[..]);
+
+    BridgeEvent::FeeScheduleUpdateScheduled {
+        token_index: _token_index,
+        tiers: _tiers.to_vec(),
+        min_fee: _min_fee,
+        max_fee: _max_fee,
+        update_time,
+    }
+    .emit();
     Ok(())
 }
 
-fn transfer_ownership(
+fn update_fees(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _new_owner: Pubkey,
+    _token_index: u64,
 ) -> ProgramResult {
-    let account_info_iter = &mut _accounts.iter();
-    let owner_account = next_account_info(account_info_iter)?;
-    let wpokt_account = next_account_info(account_info_iter)?;
-
-    if wpokt_account.owner != _program_id {
-        return Err(ProgramError::IncorrectProgramId);
-    }
-    let mut bridge_data = Bridge::unpack_from_slice(&wpokt_account.data.borrow())?;
-    if !bridge_data.is_initialized {
-        return Err(ProgramError::UninitializedAccount);
-    }
-
-    only_owner(owner_account, &bridge_data)?;
-
-    if _new_owner == Pubkey::new_from_array([0_u8; 32]) {
-        return Err(ProgramError::InvalidArgument);
+    verify_program_accounts_ownership(_program_id, _accounts)?;
+    _update_stable_fee(_program_id, _accounts)?;
+    _update_token_fee(_program_id, _accounts, _token_index)?;
+    if let Some(fee_schedule_account) = _accounts.get(2) {
+        _update_fee_schedule(_program_id, fee_schedule_account, _token_index)?;
     }
-    bridge_data.owner = _new_owner;
-    bridge_data.pack_into_slice(&mut &mut wpokt_account.data.borrow_mut()[..]);
     Ok(())
 }
 
-fn create_claimed_dictionary_pda_account(
+fn withdraw_fees(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _index: u64,
-    _chain_id: u64,
+    _amount: u64,
 ) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[2..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let payer = next_account_info(account_info_iter)?;
-    let claimed_pda_account = next_account_info(account_info_iter)?;
-    let rent_sysvar_account = next_account_info(account_info_iter)?;
-    let system_account = next_account_info(account_info_iter)?;
-
-    let (pda, bump) = ClaimedDictionary::generate_pda_key(_program_id, _chain_id, _index);
+    let treasurer_account = next_account_info(account_info_iter)?;
+    let treasury_token_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?; // the PDA account
+    let _bridge_token_account = next_account_info(account_info_iter)?; // PDA token account
+    let _mint_account = next_account_info(account_info_iter)?;
+    let _token_list_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
 
-    if !claimed_pda_account.key.eq(&pda) {
-        return Err(ProgramError::InvalidSeeds);
-    }
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
 
-    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
-    let ix = system_instruction::create_account(
-        payer.key,
-        claimed_pda_account.key,
-        rent_sysvar.minimum_balance(ClaimedDictionary::LEN),
-        ClaimedDictionary::LEN.try_into().unwrap(),
+    only_role(
         _program_id,
-    );
+        treasurer_account,
+        role_account,
+        Role::Treasurer,
+    )?;
+    when_not_paused(&bridge_data)?;
 
-    let index_bytes = _index.to_le_bytes();
-    let chain_id_bytes = _chain_id.to_le_bytes();
-    let signature_seeds = &[
-        index_bytes.as_ref(),
-        chain_id_bytes.as_ref(),
-        COMMON_BASE_SEED.as_bytes(),
-        ClaimedDictionary::BASE_SEED.as_bytes(),
-        &[bump],
-    ];
+    if _amount == 0 {
+        return Err(ProgramError::Custom(BridgeError::NothingToWithdraw as u32));
+    }
 
-    program::invoke_signed(
-        &ix,
-        &[
-            payer.clone(),
-            claimed_pda_account.clone(),
-            system_account.clone(),
-        ],
-        &[signature_seeds],
+    transfer_fees_to_treasury(
+        _program_id,
+        bridge_account,
+        &bridge_data,
+        treasury_token_account,
+        &_accounts[3..6],
+        _index,
+        Some(_amount),
     )?;
     Ok(())
 }
 
-fn create_daily_token_claims_dictionary_pda_account(
+fn withdraw_lamport_fees(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
-    _token_index: u64,
+    _amount: u64,
 ) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
     let account_info_iter = &mut _accounts.iter();
-    let payer = next_account_info(account_info_iter)?;
-    let dtc_pda_account = next_account_info(account_info_iter)?;
-    let rent_sysvar_account = next_account_info(account_info_iter)?;
-    let system_account = next_account_info(account_info_iter)?;
-
-    let (pda, bump) = DailyTokenClaimsDictionary::generate_pda_key(_program_id, _token_index);
+    let treasurer_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?; // the PDA account
+    let fee_vault_account = next_account_info(account_info_iter)?; // PDA account
+    let treasury_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
 
-    if !dtc_pda_account.key.eq(&pda) {
-        return Err(ProgramError::InvalidSeeds);
-    }
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
 
-    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
-    let ix = system_instruction::create_account(
-        payer.key,
-        dtc_pda_account.key,
-        rent_sysvar.minimum_balance(DailyTokenClaimsDictionary::LEN),
-        DailyTokenClaimsDictionary::LEN.try_into().unwrap(),
+    only_role(
         _program_id,
-    );
+        treasurer_account,
+        role_account,
+        Role::Treasurer,
+    )?;
+    when_not_paused(&bridge_data)?;
 
-    let index_bytes = _token_index.to_le_bytes();
-    let signature_seeds = &[
-        index_bytes.as_ref(),
-        COMMON_BASE_SEED.as_bytes(),
-        DailyTokenClaimsDictionary::BASE_SEED.as_bytes(),
-        &[bump],
-    ];
+    if bridge_data.treasury == Pubkey::default() {
+        return Err(ProgramError::Custom(BridgeError::TreasuryNotSet as u32));
+    }
+    if !treasury_account.key.eq(&bridge_data.treasury) {
+        return Err(ProgramError::Custom(BridgeError::NotTreasuryAccount as u32));
+    }
+    if _amount == 0 {
+        return Err(ProgramError::Custom(BridgeError::NothingToWithdraw as u32));
+    }
 
-    program::invoke_signed(
-        &ix,
-        &[
-            payer.clone(),
-            dtc_pda_account.clone(),
-            system_account.clone(),
-        ],
-        &[signature_seeds],
-    )?;
+    let (fee_vault_pda, _) = FeeVault::generate_pda_key(_program_id);
+    if !fee_vault_account.key.eq(&fee_vault_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut fee_vault_data = FeeVault::unpack_from_slice(&fee_vault_account.data.borrow())?;
+    fee_vault_data.total_fees_collected = fee_vault_data
+        .total_fees_collected
+        .checked_sub(_amount)
+        .ok_or(ProgramError::Custom(
+            BridgeError::WithdrawAboveCollectedFees as u32,
+        ))?;
+    fee_vault_data.pack_into_slice(&mut fee_vault_account.data.borrow_mut()[..]);
+
+    // the vault is owned by the Bridge program, which can debit it directly
+    **fee_vault_account.try_borrow_mut_lamports()? = fee_vault_account
+        .lamports()
+        .checked_sub(_amount)
+        .ok_or(ProgramError::Custom(BridgeError::Underflow as u32))?;
+    **treasury_account.try_borrow_mut_lamports()? = treasury_account
+        .lamports()
+        .checked_add(_amount)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+
+    BridgeEvent::LamportFeesWithdrawn {
+        to: bridge_data.treasury,
+        amount: _amount,
+    }
+    .emit();
     Ok(())
 }
-// ========================== Helper Functions ==================== //
 
-// Checks that all provided accounts are owned by the provided program_id
-fn verify_program_accounts_ownership(
+fn withdraw_fees_batch(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
+    _indices: &[u64],
 ) -> ProgramResult {
-    // verify all accounts are owned by current program
-    for account in _accounts.into_iter() {
-        if account.owner != _program_id {
-            return Err(ProgramError::Custom(
-                BridgeError::AccountNotOwnedByBridge as u32,
-            ));
-        }
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let treasurer_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?; // the PDA account
+    let role_account = next_account_info(account_info_iter)?;
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    only_role(
+        _program_id,
+        treasurer_account,
+        role_account,
+        Role::Treasurer,
+    )?;
+    when_not_paused(&bridge_data)?;
+
+    // treasury token, bridge token, mint and token list account for every index
+    let fee_accounts = &_accounts[3..];
+    if fee_accounts.len() != _indices.len() * 4 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut withdrawn = false;
+    for (index, accounts) in _indices.iter().zip(fee_accounts.chunks(4)) {
+        let amount = transfer_fees_to_treasury(
+            _program_id,
+            bridge_account,
+            &bridge_data,
+            &accounts[0],
+            &accounts[1..4],
+            *index,
+            None,
+        )?;
+        withdrawn = withdrawn || amount > 0;
+    }
+    if !withdrawn {
+        return Err(ProgramError::Custom(BridgeError::NothingToWithdraw as u32));
     }
     Ok(())
 }
 
-// Verifies that Bridge account owner initiated the transaction
-fn only_owner(_owner_account: &AccountInfo, bridge_data: &Bridge) -> ProgramResult {
-    if !_owner_account.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+// Moves collected fees of token 'index' from the Bridge token account to a token
+// account of the treasury, all of them when 'amount' is None. 'token_accounts' are
+// the Bridge token, mint and token list accounts. Returns the amount withdrawn.
+fn transfer_fees_to_treasury<'a>(
+    _program_id: &Pubkey,
+    bridge_account: &AccountInfo<'a>,
+    bridge_data: &Bridge,
+    treasury_token_account: &AccountInfo<'a>,
+    token_accounts: &[AccountInfo<'a>],
+    _index: u64,
+    _amount: Option<u64>,
+) -> Result<u64, ProgramError> {
+    let account_info_iter = &mut token_accounts.iter();
+    let bridge_token_account = next_account_info(account_info_iter)?; // PDA token account
+    let mint_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+
+    if bridge_data.treasury == Pubkey::default() {
+        return Err(ProgramError::Custom(BridgeError::TreasuryNotSet as u32));
     }
 
-    if bridge_data.owner != *_owner_account.key {
-        return Err(ProgramError::IllegalOwner);
+    let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(_program_id, _index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::Custom(BridgeError::MapKeyNotFound as u32));
     }
-    Ok(())
+
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+
+    if token_list_data.token_address != *mint_account.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    let treasury_token_data = unpack_token_account(treasury_token_account)?;
+    if treasury_token_data.owner != bridge_data.treasury
+        || treasury_token_data.mint != *mint_account.key
+    {
+        return Err(ProgramError::Custom(BridgeError::NotTreasuryAccount as u32));
+    }
+
+    let to_transfer = match _amount {
+        Some(amount) => amount,
+        None => token_list_data.total_fees_collected,
+    };
+    if to_transfer == 0 {
+        return Ok(0);
+    }
+    token_list_data.total_fees_collected = token_list_data
+        .total_fees_collected
+        .checked_sub(to_transfer)
+        .ok_or(ProgramError::Custom(
+            BridgeError::WithdrawAboveCollectedFees as u32,
+        ))?;
+    // fees of the requests so far can't be refunded any more, even if only part was withdrawn
+    token_list_data.fees_withdrawn_index = bridge_data.current_index;
+
+    let (pda, bump, seed1, seed2) = Bridge::generate_pda_key(_program_id);
+    let pda_seeds: &[&[u8]] = &[seed1.as_bytes(), seed2.as_bytes(), &[bump]];
+    let transfer_ix = spl_token_2022::instruction::transfer_checked(
+        &token_list_data.token_program,
+        bridge_token_account.key,
+        mint_account.key,
+        treasury_token_account.key,
+        &pda,
+        &[&pda],
+        to_transfer,
+        mint_decimals(mint_account)?,
+    )?;
+
+    program::invoke_signed(
+        &transfer_ix,
+        &[
+            bridge_token_account.clone(),
+            mint_account.clone(),
+            treasury_token_account.clone(),
+            bridge_account.clone(),
+        ],
+        &[pda_seeds],
+    )?;
+
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::FeesWithdrawn {
+        token_index: _index,
+        to: *treasury_token_account.key,
+        amount: to_transfer,
+    }
+    .emit();
+    Ok(to_transfer)
 }
 
-fn generate_bridge_token_pda(_program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8, String, String) {
-    let seed1 = "bridge";
-    let seed2 = "bridge_token_account";
-    let seeds = &[
-        mint.as_ref(),
-        seed1.as_bytes().as_ref(),
-        seed2.as_bytes().as_ref(),
-    ];
-    let (pda, bump) = Pubkey::find_program_address(seeds, _program_id);
+fn add_token(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _index: u64,
+    _token_address: Pubkey,
+    _fee: u64,
+    _limit: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?; // PDA Account
+    let token_list_account = next_account_info(account_info_iter)?; // PDA Account
+    let token_added_account = next_account_info(account_info_iter)?; // PDA Account
+    let mint = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+    let bridge_token_account = next_account_info(account_info_iter)?; // PDA Account
+    let token_program_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+
+    if !executor_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // the token program owning the mint handles every transfer of the token
+    if *mint.key != _token_address {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if *mint.owner != spl_token::id() && *mint.owner != spl_token_2022::id() {
+        return Err(ProgramError::Custom(
+            BridgeError::UnsupportedTokenProgram as u32,
+        ));
+    }
+    if *token_program_account.key != *mint.owner {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let decimals = mint_decimals(mint)?;
+
+    // create and initialize TokenAdded dictionary item account
+    let (token_added_pda, token_added_bump, token_added_seed1, token_added_seed2) =
+        TokenAddedDictionary::generate_pda_key(_program_id, mint.key);
+    if !token_added_account.key.eq(&token_added_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let create_token_added_pda_ix = system_instruction::create_account(
+        executor_account.key,
+        token_added_account.key,
+        rent_sysvar.minimum_balance(TokenAddedDictionary::LEN),
+        TokenAddedDictionary::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &create_token_added_pda_ix,
+        &[
+            executor_account.clone(),
+            token_added_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            _token_address.as_ref(),
+            token_added_seed1.as_ref(),
+            token_added_seed2.as_ref(),
+            &[token_added_bump],
+        ]],
+    )?;
+
+    let mut token_added_data =
+        TokenAddedDictionary::unpack_from_slice(&token_added_account.data.borrow())?;
+    token_added_data.token_added = true;
+    token_added_data.pack_into_slice(&mut &mut token_added_account.data.borrow_mut()[..]);
+
+    // // create and initialize TokenList dictionary item account
+    let (token_list_pda, token_list_bump, token_list_seed1, token_list_seed2) =
+        TokenListDictionary::generate_pda_key(_program_id, _index);
+
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let create_token_list_pda_ix = system_instruction::create_account(
+        executor_account.key,
+        token_list_account.key,
+        rent_sysvar.minimum_balance(TokenListDictionary::LEN),
+        TokenListDictionary::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &create_token_list_pda_ix,
+        &[
+            executor_account.clone(),
+            token_list_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            _index.to_le_bytes().as_ref(),
+            token_list_seed1.as_ref(),
+            token_list_seed2.as_ref(),
+            &[token_list_bump],
+        ]],
+    )?;
+
+    let token_data_list = TokenListDictionary {
+        is_initialized: true,
+        token_address: _token_address,
+        exists: true,
+        paused: false,
+        // total fees collected
+        total_fees_collected: 0,
+        // current fee
+        fee: 0,
+        // fee update time
+        fee_update_time: 0,
+        // new fee
+        new_fee: 0,
+        // claim limit
+        limit: 0,
+        // claim limit window
+        limit_window: SECONDS_PER_DAY,
+        mint_burn: false,
+        recipient_limit: 0,
+        settlement_threshold: 0,
+        settlement_delay: 0,
+        token_program: *mint.owner,
+        local_decimals: decimals,
+        remote_decimals: decimals,
+        lamport_fee: 0,
+        fee_rounding: RoundingMode::Floor,
+        fees_withdrawn_index: 0,
+    };
+    token_data_list.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+
+    // create Bridge Token account for the mint under the token program owning it
+    let (bridge_token_pda, bridge_token_bump, bridge_token_seed1, bridge_token_seed2) =
+        generate_bridge_token_pda(_program_id, mint.key);
+    if !bridge_token_account.key.eq(&bridge_token_pda) {
+        return Err(ProgramError::Custom(
+            BridgeError::TokenAccountKeyMismatch as u32,
+        ));
+    }
+
+    let bridge_token_account_len = token_account_len(mint)?;
+    let create_token_account_ix = system_instruction::create_account(
+        executor_account.key,
+        bridge_token_account.key,
+        rent_sysvar.minimum_balance(bridge_token_account_len),
+        bridge_token_account_len.try_into().unwrap(),
+        mint.owner,
+    );
+    program::invoke_signed(
+        &create_token_account_ix,
+        &[
+            executor_account.clone(),
+            bridge_token_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            _token_address.as_ref(),
+            bridge_token_seed1.as_ref(),
+            bridge_token_seed2.as_ref(),
+            &[bridge_token_bump],
+        ]],
+    )?;
+
+    // initialize Bridge Token Account, authority set to Bridge PDA Account
+    let initialize_token_account_ix = spl_token_2022::instruction::initialize_account3(
+        mint.owner,
+        bridge_token_account.key,
+        mint.key,
+        bridge_account.key,
+    )?;
+    program::invoke(
+        &initialize_token_account_ix,
+        &[
+            bridge_token_account.clone(),
+            mint.clone(),
+            token_program_account.clone(),
+        ],
+    )?;
+
+    BridgeEvent::TokenAdded {
+        token_index: _index,
+        token_address: _token_address,
+    }
+    .emit();
+    Ok(())
+}
+
+fn pause_token(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(&_program_id, _accounts[1..].as_ref())?;
+
+    let account_info_iter = &mut _accounts.iter();
+    let pauser_account = next_account_info(account_info_iter)?;
+    let _bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
+
+    if !pauser_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+
+    only_role(_program_id, pauser_account, role_account, Role::Pauser)?;
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if token_list_data.paused == true {
+        return Err(ProgramError::Custom(BridgeError::TokenAlreadyPaused as u32));
+    }
+
+    token_list_data.paused = true;
+    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::TokenPaused {
+        token_index: _token_index,
+    }
+    .emit();
+    Ok(())
+}
+
+fn unpause_token(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(&_program_id, _accounts[1..].as_ref())?;
+
+    let account_info_iter = &mut _accounts.iter();
+    let pauser_account = next_account_info(account_info_iter)?;
+    let _bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
+
+    if !pauser_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+
+    only_role(_program_id, pauser_account, role_account, Role::Pauser)?;
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if token_list_data.paused == false {
+        return Err(ProgramError::Custom(
+            BridgeError::TokenAlreadyUnaused as u32,
+        ));
+    }
+
+    token_list_data.paused = false;
+    token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    BridgeEvent::TokenUnpaused {
+        token_index: _token_index,
+    }
+    .emit();
+    Ok(())
+}
+
+fn set_token_mint_burn(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _mint_burn: bool,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+    token_list_data.mint_burn = _mint_burn;
+    token_list_data.pack_into_slice(&mut token_list_account.data.borrow_mut()[..]);
+
+    BridgeEvent::TokenMintBurnSet {
+        token_index: _token_index,
+        mint_burn: _mint_burn,
+    }
+    .emit();
+    Ok(())
+}
+
+fn add_chain(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _chain_id: u64,
+    _stable_fee: u64,
+    _address_kinds: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let chain_registry_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    if _chain_id == bridge_data.chain_id {
+        return Err(ProgramError::Custom(BridgeError::RequestToSameChain as u32));
+    }
+    if !RemoteAddress::is_valid_kinds(_address_kinds) {
+        return Err(ProgramError::Custom(
+            BridgeError::UnsupportedAddressKind as u32,
+        ));
+    }
+
+    let (pda, bump) = ChainRegistry::generate_pda_key(_program_id, _chain_id);
+    if !chain_registry_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // a disabled chain keeps its account and is enabled again in place
+    if chain_registry_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            executor_account.key,
+            chain_registry_account.key,
+            rent_sysvar.minimum_balance(ChainRegistry::LEN),
+            ChainRegistry::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                executor_account.clone(),
+                chain_registry_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                _chain_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                ChainRegistry::BASE_SEED.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+    } else {
+        let chain_registry_data =
+            ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+        if chain_registry_data.enabled {
+            return Err(ProgramError::Custom(BridgeError::ChainAlreadyAdded as u32));
+        }
+    }
+
+    let chain_registry_data = ChainRegistry {
+        is_initialized: true,
+        chain_id: _chain_id,
+        enabled: true,
+        paused: false,
+        stable_fee: _stable_fee,
+        address_kinds: _address_kinds,
+    };
+    chain_registry_data.pack_into_slice(&mut chain_registry_account.data.borrow_mut()[..]);
+
+    BridgeEvent::ChainAdded {
+        chain_id: _chain_id,
+        stable_fee: _stable_fee,
+        address_kinds: _address_kinds,
+    }
+    .emit();
+    Ok(())
+}
+
+// Timelocked, the chain's stable fee replaces the Bridge's in quote_fee
+fn update_chain(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _chain_id: u64,
+    _stable_fee: u64,
+    _address_kinds: u8,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let chain_registry_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    if !RemoteAddress::is_valid_kinds(_address_kinds) {
+        return Err(ProgramError::Custom(
+            BridgeError::UnsupportedAddressKind as u32,
+        ));
+    }
+
+    let (pda, _) = ChainRegistry::generate_pda_key(_program_id, _chain_id);
+    if !chain_registry_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut chain_registry_data =
+        ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+    if !chain_registry_data.enabled {
+        return Err(ProgramError::Custom(BridgeError::ChainNotRegistered as u32));
+    }
+
+    chain_registry_data.stable_fee = _stable_fee;
+    chain_registry_data.address_kinds = _address_kinds;
+    chain_registry_data.pack_into_slice(&mut chain_registry_account.data.borrow_mut()[..]);
+
+    BridgeEvent::ChainUpdated {
+        chain_id: _chain_id,
+        stable_fee: _stable_fee,
+        address_kinds: _address_kinds,
+    }
+    .emit();
+    Ok(())
+}
+
+fn set_chain_paused(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _chain_id: u64,
+    _paused: bool,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let chain_registry_account = next_account_info(account_info_iter)?;
+
+    only_owner(_program_id, owner_account, bridge_account)?;
+
+    let (pda, _) = ChainRegistry::generate_pda_key(_program_id, _chain_id);
+    if !chain_registry_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut chain_registry_data =
+        ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+    if !chain_registry_data.enabled {
+        return Err(ProgramError::Custom(BridgeError::ChainNotRegistered as u32));
+    }
+    if chain_registry_data.paused == _paused {
+        let error = if _paused {
+            BridgeError::ChainAlreadyPaused
+        } else {
+            BridgeError::ChainNotPaused
+        };
+        return Err(ProgramError::Custom(error as u32));
+    }
+
+    chain_registry_data.paused = _paused;
+    chain_registry_data.pack_into_slice(&mut chain_registry_account.data.borrow_mut()[..]);
+
+    if _paused {
+        BridgeEvent::ChainPaused {
+            chain_id: _chain_id,
+        }
+        .emit();
+    } else {
+        BridgeEvent::ChainUnpaused {
+            chain_id: _chain_id,
+        }
+        .emit();
+    }
+    Ok(())
+}
+
+fn disable_chain(_program_id: &Pubkey, _accounts: &[AccountInfo], _chain_id: u64) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let chain_registry_account = next_account_info(account_info_iter)?;
+
+    only_owner(_program_id, owner_account, bridge_account)?;
+
+    let (pda, _) = ChainRegistry::generate_pda_key(_program_id, _chain_id);
+    if !chain_registry_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut chain_registry_data =
+        ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+    if !chain_registry_data.enabled {
+        return Err(ProgramError::Custom(BridgeError::ChainNotRegistered as u32));
+    }
+
+    chain_registry_data.enabled = false;
+    chain_registry_data.pack_into_slice(&mut chain_registry_account.data.borrow_mut()[..]);
+
+    BridgeEvent::ChainDisabled {
+        chain_id: _chain_id,
+    }
+    .emit();
+    Ok(())
+}
+
+fn set_guardian(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _guardian: Pubkey,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = only_owner(_program_id, owner_account, bridge_account)?;
+
+    let previous_guardian = bridge_data.guardian;
+    bridge_data.guardian = _guardian;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::GuardianUpdated {
+        previous_guardian,
+        new_guardian: _guardian,
+    }
+    .emit();
+    Ok(())
+}
+
+fn pause_bridge(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let caller_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    if !caller_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    // the zero key can never sign, so an unset guardian leaves pausing to the owner
+    if bridge_data.guardian != *caller_account.key && bridge_data.owner != *caller_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    when_not_paused(&bridge_data)?;
+
+    bridge_data.paused = true;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::BridgePaused {
+        by: *caller_account.key,
+    }
+    .emit();
+    Ok(())
+}
+
+fn unpause_bridge(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = only_owner(_program_id, owner_account, bridge_account)?;
+
+    if !bridge_data.paused {
+        return Err(ProgramError::Custom(BridgeError::BridgeNotPaused as u32));
+    }
+
+    bridge_data.paused = false;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::BridgeUnpaused.emit();
+    Ok(())
+}
+
+fn grant_role(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _role: Role,
+    _account: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let (pda, bump) = RoleDictionary::generate_pda_key(_program_id, _role, &_account);
+    if !role_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // a revoked role keeps its account and is granted again in place
+    if role_account.data_is_empty() {
+        let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+        let ix = system_instruction::create_account(
+            executor_account.key,
+            role_account.key,
+            rent_sysvar.minimum_balance(RoleDictionary::LEN),
+            RoleDictionary::LEN.try_into().unwrap(),
+            _program_id,
+        );
+        program::invoke_signed(
+            &ix,
+            &[
+                executor_account.clone(),
+                role_account.clone(),
+                system_program_account.clone(),
+            ],
+            &[&[
+                &[_role as u8],
+                _account.as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                RoleDictionary::BASE_SEED.as_bytes(),
+                &[bump],
+            ]],
+        )?;
+    } else {
+        let role_data = RoleDictionary::unpack_from_slice(&role_account.data.borrow())?;
+        if role_data.has_role {
+            return Err(ProgramError::Custom(BridgeError::RoleAlreadyGranted as u32));
+        }
+    }
+
+    let role_data = RoleDictionary {
+        is_initialized: true,
+        has_role: true,
+    };
+    role_data.pack_into_slice(&mut role_account.data.borrow_mut()[..]);
+
+    BridgeEvent::RoleGranted {
+        role: _role,
+        account: _account,
+    }
+    .emit();
+    Ok(())
+}
+
+fn revoke_role(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _role: Role,
+    _account: Pubkey,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let role_account = next_account_info(account_info_iter)?;
+
+    only_owner(_program_id, owner_account, bridge_account)?;
+
+    let (pda, _) = RoleDictionary::generate_pda_key(_program_id, _role, &_account);
+    if !role_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut role_data = RoleDictionary::unpack_from_slice(&role_account.data.borrow())?;
+    if !role_data.has_role {
+        return Err(ProgramError::Custom(BridgeError::MissingRole as u32));
+    }
+
+    role_data.has_role = false;
+    role_data.pack_into_slice(&mut role_account.data.borrow_mut()[..]);
+
+    BridgeEvent::RoleRevoked {
+        role: _role,
+        account: _account,
+    }
+    .emit();
+    Ok(())
+}
+
+/// Accounts expected
+/// 1. `[writable]` the TokenList account
+fn _update_token_fee(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+) -> ProgramResult {
+    let token_list_account = &_accounts[1];
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+
+    if token_list_data.fee_update_time == 0 {
+        return Ok(());
+    }
+
+    if current_timestamp as u64 > token_list_data.fee_update_time {
+        token_list_data.fee = token_list_data.new_fee;
+        token_list_data.fee_update_time = 0;
+        token_list_data.pack_into_slice(&mut &mut token_list_account.data.borrow_mut()[..]);
+    }
+
+    Ok(())
+}
+
+fn _update_fee_schedule(
+    _program_id: &Pubkey,
+    fee_schedule_account: &AccountInfo,
+    _token_index: u64,
+) -> ProgramResult {
+    let (fee_schedule_pda, _) = FeeSchedule::generate_pda_key(_program_id, _token_index);
+    if !fee_schedule_account.key.eq(&fee_schedule_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut fee_schedule_data =
+        FeeSchedule::unpack_from_slice(&fee_schedule_account.data.borrow())?;
+    if fee_schedule_data.update_time == 0 {
+        return Ok(());
+    }
+
+    if Clock::get()?.unix_timestamp as u64 > fee_schedule_data.update_time {
+        fee_schedule_data.tiers = std::mem::take(&mut fee_schedule_data.new_tiers);
+        fee_schedule_data.min_fee = fee_schedule_data.new_min_fee;
+        fee_schedule_data.max_fee = fee_schedule_data.new_max_fee;
+        fee_schedule_data.update_time = 0;
+        fee_schedule_data.pack_into_slice(&mut fee_schedule_account.data.borrow_mut()[..]);
+    }
+
+    Ok(())
+}
+
+/// Accounts Expected
+/// 0. `[writable]` the Bridge account
+fn _update_stable_fee(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    let bridge_account: &AccountInfo = &_accounts[0];
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    if bridge_data.stable_fee_update_time == 0 {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    let current_timestamp = clock.unix_timestamp;
+
+    if current_timestamp as u64 > bridge_data.stable_fee_update_time {
+        bridge_data.stable_fee = bridge_data.new_stable_fee;
+        bridge_data.stable_fee_update_time = 0;
+        bridge_data.pack_into_slice(&mut &mut bridge_account.data.borrow_mut()[..]);
+    }
+
+    Ok(())
+}
+
+fn calculate_fee(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _amount: u64,
+) -> ProgramResult {
+    let fee = quote_fee(_program_id, _accounts, _token_index, _amount)?;
+    let token_list_data = TokenListDictionary::unpack_from_slice(&_accounts[1].data.borrow())?;
+
+    // tokens with a lamport fee take nothing out of the amount
+    let (fee, lamport_fee) = if token_list_data.lamport_fee > 0 {
+        (0, token_list_data.lamport_fee)
+    } else {
+        (fee, 0)
+    };
+    let (_, dust) = token_list_data.to_remote_amount(
+        _amount
+            .checked_sub(fee)
+            .ok_or(ProgramError::Custom(BridgeError::AmountBelowFee as u32))?,
+    )?;
+    let fee_quote = FeeQuote {
+        fee,
+        net_amount: _amount - fee - dust,
+        lamport_fee,
+    };
+    set_return_data(
+        &fee_quote
+            .try_to_vec()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    Ok(())
+}
+
+/// Fee taken out of '_amount' of token '_token_index'
+///
+/// Accounts expected
+/// 0. `[]` the Bridge account
+/// 1. `[]` the 'token_list' dictionary account
+/// 2. `[]` (optional) the ChainRegistry account of the destination chain
+/// 3. `[]` (optional) the FeeSchedule account of '_token_index', after account 2
+fn quote_fee(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _amount: u64,
+) -> Result<u64, ProgramError> {
+    verify_program_accounts_ownership(_program_id, _accounts)?;
+
+    let account_info_iter = &mut _accounts.iter();
+    let bridge_account = next_account_info(account_info_iter)?;
+    let token_list_account = next_account_info(account_info_iter)?;
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    // the destination chain can override the Bridge's stable fee
+    let mut stable_fee = bridge_data.stable_fee;
+    if let Some(chain_registry_account) = account_info_iter.next() {
+        let chain_registry_data =
+            ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+        let (chain_registry_pda, _) =
+            ChainRegistry::generate_pda_key(_program_id, chain_registry_data.chain_id);
+        if !chain_registry_account.key.eq(&chain_registry_pda) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if chain_registry_data.stable_fee != 0 {
+            stable_fee = chain_registry_data.stable_fee;
+        }
+    }
+
+    let (token_list_pda, _, _, _) =
+        TokenListDictionary::generate_pda_key(_program_id, _token_index);
+    if !token_list_account.key.eq(&token_list_pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let token_list_data =
+        TokenListDictionary::unpack_from_slice(&token_list_account.data.borrow())?;
+
+    // a FeeSchedule with tiers replaces the token's proportional fee
+    if let Some(fee_schedule_account) = account_info_iter.next() {
+        let (fee_schedule_pda, _) = FeeSchedule::generate_pda_key(_program_id, _token_index);
+        if !fee_schedule_account.key.eq(&fee_schedule_pda) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let fee_schedule_data =
+            FeeSchedule::unpack_from_slice(&fee_schedule_account.data.borrow())?;
+        if !fee_schedule_data.tiers.is_empty() {
+            return scheduled_fee(&fee_schedule_data, _amount, token_list_data.fee_rounding);
+        }
+    }
+
+    // the token's fee overrides the stable fee, a rate of TEN_POW_18 or more charges nothing
+    let rate = if token_list_data.fee != 0 {
+        token_list_data.fee
+    } else {
+        stable_fee
+    };
+    if rate >= TEN_POW_18 {
+        return Ok(0);
+    }
+    apply_rate(_amount, rate, token_list_data.fee_rounding)
+}
+
+// Fee of 'amount' under a FeeSchedule with tiers
+fn scheduled_fee(
+    fee_schedule_data: &FeeSchedule,
+    _amount: u64,
+    rounding: RoundingMode,
+) -> Result<u64, ProgramError> {
+    let fee = apply_rate(_amount, fee_schedule_data.rate(_amount), rounding)?;
+    let mut fee = fee.max(fee_schedule_data.min_fee);
+    if fee_schedule_data.max_fee > 0 {
+        fee = fee.min(fee_schedule_data.max_fee);
+    }
+    if fee > _amount {
+        return Err(ProgramError::Custom(BridgeError::AmountBelowFee as u32));
+    }
+    Ok(fee)
+}
+
+// Tiers start at zero, strictly increase and charge less than the whole amount
+fn validate_fee_schedule(tiers: &[FeeTier], min_fee: u64, max_fee: u64) -> ProgramResult {
+    let invalid = tiers.len() > MAX_FEE_TIERS
+        || tiers.first().is_some_and(|tier| tier.threshold != 0)
+        || tiers
+            .windows(2)
+            .any(|pair| pair[0].threshold >= pair[1].threshold)
+        || tiers.iter().any(|tier| tier.rate >= TEN_POW_18)
+        || (max_fee > 0 && min_fee > max_fee);
+    if invalid {
+        return Err(ProgramError::Custom(BridgeError::InvalidFeeSchedule as u32));
+    }
+    Ok(())
+}
+
+fn renounce_ownership(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _confirm: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = only_owner(_program_id, owner_account, bridge_account)?;
+
+    if !_confirm {
+        return Err(ProgramError::Custom(
+            BridgeError::RenounceNotConfirmed as u32,
+        ));
+    }
+
+    bridge_data.owner = Pubkey::new_from_array([0_u8; 32]);
+    bridge_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::OwnershipTransferred {
+        previous_owner: *owner_account.key,
+        new_owner: bridge_data.owner,
+    }
+    .emit();
+    Ok(())
+}
+
+fn propose_owner(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _new_owner: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = only_owner(_program_id, owner_account, bridge_account)?;
+
+    if _new_owner == Pubkey::new_from_array([0_u8; 32]) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    bridge_data.pending_owner = _new_owner;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::OwnershipProposed {
+        owner: *owner_account.key,
+        pending_owner: _new_owner,
+    }
+    .emit();
+    Ok(())
+}
+
+fn accept_ownership(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let pending_owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+
+    if bridge_data.pending_owner == Pubkey::new_from_array([0_u8; 32]) {
+        return Err(ProgramError::Custom(BridgeError::NoPendingOwner as u32));
+    }
+    if !pending_owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if bridge_data.pending_owner != *pending_owner_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let previous_owner = bridge_data.owner;
+    bridge_data.owner = bridge_data.pending_owner;
+    bridge_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::OwnershipTransferred {
+        previous_owner,
+        new_owner: bridge_data.owner,
+    }
+    .emit();
+    Ok(())
+}
+
+fn cancel_ownership_proposal(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = only_owner(_program_id, owner_account, bridge_account)?;
+
+    if bridge_data.pending_owner == Pubkey::new_from_array([0_u8; 32]) {
+        return Err(ProgramError::Custom(BridgeError::NoPendingOwner as u32));
+    }
+    let pending_owner = bridge_data.pending_owner;
+    bridge_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+    BridgeEvent::OwnershipProposalCancelled { pending_owner }.emit();
+    Ok(())
+}
+
+fn queue_admin_action(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _instruction: Vec<u8>,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let proposer_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let admin_action_account = next_account_info(account_info_iter)?; // PDA account
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    if !proposer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if _instruction.len() > MAX_ADMIN_ACTION_LEN {
+        return Err(ProgramError::Custom(BridgeError::ActionTooLarge as u32));
+    }
+    let instruction = BridgeInstruction::try_from_slice(&_instruction)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let mut bridge_data = match timelock_role(&instruction)? {
+        None => only_owner(_program_id, proposer_account, bridge_account)?,
+        Some(role) => {
+            let role_account = next_account_info(account_info_iter)?;
+            only_role(_program_id, proposer_account, role_account, role)?;
+            unpack_bridge(_program_id, bridge_account)?
+        }
+    };
+
+    let action_id = bridge_data.action_count;
+    let (pda, bump) = AdminAction::generate_pda_key(_program_id, action_id);
+    if !admin_action_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        proposer_account.key,
+        admin_action_account.key,
+        rent_sysvar.minimum_balance(AdminAction::LEN),
+        AdminAction::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            proposer_account.clone(),
+            admin_action_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            action_id.to_le_bytes().as_ref(),
+            COMMON_BASE_SEED.as_bytes(),
+            AdminAction::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    let clock = Clock::get()?;
+    let eta = (clock.unix_timestamp as u64)
+        .checked_add(bridge_data.timelock_delay)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+
+    let admin_action_data = AdminAction {
+        is_initialized: true,
+        proposer: *proposer_account.key,
+        eta,
+        executed: false,
+        cancelled: false,
+        instruction: _instruction,
+    };
+    admin_action_data.pack_into_slice(&mut admin_action_account.data.borrow_mut()[..]);
+
+    bridge_data.action_count = bridge_data
+        .action_count
+        .checked_add(1)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::AdminActionQueued {
+        action_id,
+        proposer: *proposer_account.key,
+        eta,
+    }
+    .emit();
+    Ok(())
+}
+
+fn execute_admin_action(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _action_id: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[0..1].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let admin_action_account = next_account_info(account_info_iter)?; // PDA account
+
+    let (pda, _) = AdminAction::generate_pda_key(_program_id, _action_id);
+    if !admin_action_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut admin_action_data =
+        AdminAction::unpack_from_slice(&admin_action_account.data.borrow())?;
+    if !admin_action_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if admin_action_data.executed || admin_action_data.cancelled {
+        return Err(ProgramError::Custom(BridgeError::ActionAlreadyDone as u32));
+    }
+
+    let clock = Clock::get()?;
+    if (clock.unix_timestamp as u64) < admin_action_data.eta {
+        return Err(ProgramError::Custom(BridgeError::ActionNotReady as u32));
+    }
+
+    let instruction = BridgeInstruction::try_from_slice(&admin_action_data.instruction)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    admin_action_data.executed = true;
+    admin_action_data.pack_into_slice(&mut admin_action_account.data.borrow_mut()[..]);
+
+    Processor::process_admin_action(_program_id, &_accounts[1..], instruction)?;
+
+    BridgeEvent::AdminActionExecuted {
+        action_id: _action_id,
+    }
+    .emit();
+    Ok(())
+}
+
+fn cancel_admin_action(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _action_id: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let caller_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let admin_action_account = next_account_info(account_info_iter)?; // PDA account
+
+    if !caller_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    // the zero key can never sign, so an unset guardian leaves cancelling to the owner
+    if bridge_data.guardian != *caller_account.key && bridge_data.owner != *caller_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let (pda, _) = AdminAction::generate_pda_key(_program_id, _action_id);
+    if !admin_action_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut admin_action_data =
+        AdminAction::unpack_from_slice(&admin_action_account.data.borrow())?;
+    if !admin_action_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if admin_action_data.executed || admin_action_data.cancelled {
+        return Err(ProgramError::Custom(BridgeError::ActionAlreadyDone as u32));
+    }
+
+    admin_action_data.cancelled = true;
+    admin_action_data.pack_into_slice(&mut admin_action_account.data.borrow_mut()[..]);
+
+    BridgeEvent::AdminActionCancelled {
+        action_id: _action_id,
+    }
+    .emit();
+    Ok(())
+}
+
+fn update_timelock_delay(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _timelock_delay: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    bridge_data.timelock_delay = _timelock_delay;
+    bridge_data.fee_update_duration = _timelock_delay;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::TimelockDelayUpdated {
+        timelock_delay: _timelock_delay,
+    }
+    .emit();
+    Ok(())
+}
+
+fn set_treasury(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _treasury: Pubkey,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+
+    let mut bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    let previous_treasury = bridge_data.treasury;
+    bridge_data.treasury = _treasury;
+    bridge_data.pack_into_slice(&mut bridge_account.data.borrow_mut()[..]);
+
+    BridgeEvent::TreasuryUpdated {
+        previous_treasury,
+        new_treasury: _treasury,
+    }
+    .emit();
+    Ok(())
+}
+
+fn create_owner_multisig(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _signers: &[Pubkey],
+    _threshold: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let owner_multisig_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    only_owner(_program_id, owner_account, bridge_account)?;
+
+    let (pda, bump) = OwnerMultisig::generate_pda_key(_program_id);
+    if !owner_multisig_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mut owner_multisig_data = OwnerMultisig {
+        is_initialized: true,
+        ..OwnerMultisig::default()
+    };
+    set_multisig_signers(&mut owner_multisig_data, _signers, _threshold)?;
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        owner_account.key,
+        owner_multisig_account.key,
+        rent_sysvar.minimum_balance(OwnerMultisig::LEN),
+        OwnerMultisig::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            owner_account.clone(),
+            owner_multisig_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            COMMON_BASE_SEED.as_bytes(),
+            OwnerMultisig::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    owner_multisig_data.pack_into_slice(&mut owner_multisig_account.data.borrow_mut()[..]);
+    BridgeEvent::OwnerMultisigUpdated {
+        signers: _signers.to_vec(),
+        threshold: _threshold,
+    }
+    .emit();
+    Ok(())
+}
+
+fn update_owner_multisig(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _signers: &[Pubkey],
+    _threshold: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let authority_account = next_account_info(account_info_iter)?;
+    let owner_multisig_account = next_account_info(account_info_iter)?;
+
+    // only the multisig itself, through an approved transaction
+    let (authority, _) = OwnerMultisig::generate_authority_key(_program_id);
+    if !authority_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if !authority_account.key.eq(&authority) {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut owner_multisig_data = unpack_owner_multisig(_program_id, owner_multisig_account)?;
+    set_multisig_signers(&mut owner_multisig_data, _signers, _threshold)?;
+    owner_multisig_data.signers_seqno = owner_multisig_data
+        .signers_seqno
+        .checked_add(1)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+    owner_multisig_data.pack_into_slice(&mut owner_multisig_account.data.borrow_mut()[..]);
+
+    BridgeEvent::OwnerMultisigUpdated {
+        signers: _signers.to_vec(),
+        threshold: _threshold,
+    }
+    .emit();
+    Ok(())
+}
+
+fn propose_multisig_transaction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _transaction_accounts: Vec<TransactionAccount>,
+    _instruction: Vec<u8>,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let proposer_account = next_account_info(account_info_iter)?;
+    let owner_multisig_account = next_account_info(account_info_iter)?;
+    let multisig_transaction_account = next_account_info(account_info_iter)?; // PDA account
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    let mut owner_multisig_data = unpack_owner_multisig(_program_id, owner_multisig_account)?;
+    let position = multisig_signer_position(proposer_account, &owner_multisig_data)?;
+
+    if _transaction_accounts.len() > MAX_MULTISIG_TRANSACTION_ACCOUNTS
+        || _instruction.len() > MAX_MULTISIG_TRANSACTION_LEN
+    {
+        return Err(ProgramError::Custom(
+            BridgeError::TransactionTooLarge as u32,
+        ));
+    }
+    BridgeInstruction::try_from_slice(&_instruction)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let transaction_id = owner_multisig_data.transaction_count;
+    let (pda, bump) = MultisigTransaction::generate_pda_key(_program_id, transaction_id);
+    if !multisig_transaction_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        proposer_account.key,
+        multisig_transaction_account.key,
+        rent_sysvar.minimum_balance(MultisigTransaction::LEN),
+        MultisigTransaction::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            proposer_account.clone(),
+            multisig_transaction_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            transaction_id.to_le_bytes().as_ref(),
+            COMMON_BASE_SEED.as_bytes(),
+            MultisigTransaction::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    let multisig_transaction_data = MultisigTransaction {
+        is_initialized: true,
+        proposer: *proposer_account.key,
+        signers_seqno: owner_multisig_data.signers_seqno,
+        approvals: 1 << position,
+        executed: false,
+        accounts: _transaction_accounts,
+        instruction: _instruction,
+    };
+    multisig_transaction_data
+        .pack_into_slice(&mut multisig_transaction_account.data.borrow_mut()[..]);
+
+    owner_multisig_data.transaction_count = owner_multisig_data
+        .transaction_count
+        .checked_add(1)
+        .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+    owner_multisig_data.pack_into_slice(&mut owner_multisig_account.data.borrow_mut()[..]);
+
+    BridgeEvent::MultisigTransactionProposed {
+        transaction_id,
+        proposer: *proposer_account.key,
+    }
+    .emit();
+    Ok(())
+}
+
+fn approve_multisig_transaction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _transaction_id: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let signer_account = next_account_info(account_info_iter)?;
+    let owner_multisig_account = next_account_info(account_info_iter)?;
+    let multisig_transaction_account = next_account_info(account_info_iter)?; // PDA account
+
+    let owner_multisig_data = unpack_owner_multisig(_program_id, owner_multisig_account)?;
+    let position = multisig_signer_position(signer_account, &owner_multisig_data)?;
+
+    let mut multisig_transaction_data = unpack_multisig_transaction(
+        _program_id,
+        multisig_transaction_account,
+        _transaction_id,
+        &owner_multisig_data,
+    )?;
+    if multisig_transaction_data.approvals & (1 << position) != 0 {
+        return Err(ProgramError::Custom(BridgeError::AlreadyApproved as u32));
+    }
+
+    multisig_transaction_data.approvals |= 1 << position;
+    multisig_transaction_data
+        .pack_into_slice(&mut multisig_transaction_account.data.borrow_mut()[..]);
+
+    BridgeEvent::MultisigTransactionApproved {
+        transaction_id: _transaction_id,
+        signer: *signer_account.key,
+    }
+    .emit();
+    Ok(())
+}
+
+fn execute_multisig_transaction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _transaction_id: u64,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[0..2].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let owner_multisig_account = next_account_info(account_info_iter)?;
+    let multisig_transaction_account = next_account_info(account_info_iter)?; // PDA account
+    let bridge_program_account = next_account_info(account_info_iter)?;
+    let transaction_accounts = &_accounts[3..];
+
+    if !bridge_program_account.key.eq(_program_id) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let owner_multisig_data = unpack_owner_multisig(_program_id, owner_multisig_account)?;
+    let mut multisig_transaction_data = unpack_multisig_transaction(
+        _program_id,
+        multisig_transaction_account,
+        _transaction_id,
+        &owner_multisig_data,
+    )?;
+    if multisig_transaction_data.approvals.count_ones() < owner_multisig_data.threshold as u32 {
+        return Err(ProgramError::Custom(
+            BridgeError::InsufficientApprovals as u32,
+        ));
+    }
+
+    // the accounts were approved alongwith the instruction, so they must match
+    if transaction_accounts.len() != multisig_transaction_data.accounts.len()
+        || transaction_accounts
+            .iter()
+            .zip(multisig_transaction_data.accounts.iter())
+            .any(|(account, transaction_account)| account.key != &transaction_account.pubkey)
+    {
+        return Err(ProgramError::Custom(
+            BridgeError::TransactionAccountsMismatch as u32,
+        ));
+    }
+
+    multisig_transaction_data.executed = true;
+    multisig_transaction_data
+        .pack_into_slice(&mut multisig_transaction_account.data.borrow_mut()[..]);
+
+    let (authority, authority_bump) = OwnerMultisig::generate_authority_key(_program_id);
+    let account_metas = multisig_transaction_data
+        .accounts
+        .iter()
+        .map(|transaction_account| {
+            let is_signer = transaction_account.pubkey == authority;
+            if transaction_account.is_writable {
+                AccountMeta::new(transaction_account.pubkey, is_signer)
+            } else {
+                AccountMeta::new_readonly(transaction_account.pubkey, is_signer)
+            }
+        })
+        .collect();
+    let ix = Instruction::new_with_bytes(
+        *_program_id,
+        &multisig_transaction_data.instruction,
+        account_metas,
+    );
+    program::invoke_signed(
+        &ix,
+        &_accounts[2..],
+        &[&[
+            COMMON_BASE_SEED.as_bytes(),
+            OwnerMultisig::AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ]],
+    )?;
+
+    BridgeEvent::MultisigTransactionExecuted {
+        transaction_id: _transaction_id,
+    }
+    .emit();
+    Ok(())
+}
+
+fn create_claimed_dictionary_pda_account(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _index: u64,
+    _chain_id: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let claimed_pda_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_account = next_account_info(account_info_iter)?;
+
+    let (pda, bump) = ClaimedDictionary::generate_pda_key(_program_id, _chain_id, _index);
+
+    if !claimed_pda_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        claimed_pda_account.key,
+        rent_sysvar.minimum_balance(ClaimedDictionary::LEN),
+        ClaimedDictionary::LEN.try_into().unwrap(),
+        _program_id,
+    );
+
+    let index_bytes = _index.to_le_bytes();
+    let chain_id_bytes = _chain_id.to_le_bytes();
+    let signature_seeds = &[
+        chain_id_bytes.as_ref(),
+        index_bytes.as_ref(),
+        COMMON_BASE_SEED.as_bytes(),
+        ClaimedDictionary::BASE_SEED.as_bytes(),
+        &[bump],
+    ];
+
+    program::invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            claimed_pda_account.clone(),
+            system_account.clone(),
+        ],
+        &[signature_seeds],
+    )?;
+    Ok(())
+}
+
+fn create_daily_token_claims_dictionary_pda_account(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let dtc_pda_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_account = next_account_info(account_info_iter)?;
+
+    let (pda, bump) = DailyTokenClaimsDictionary::generate_pda_key(_program_id, _token_index);
+
+    if !dtc_pda_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        dtc_pda_account.key,
+        rent_sysvar.minimum_balance(DailyTokenClaimsDictionary::LEN),
+        DailyTokenClaimsDictionary::LEN.try_into().unwrap(),
+        _program_id,
+    );
+
+    let index_bytes = _token_index.to_le_bytes();
+    let signature_seeds = &[
+        index_bytes.as_ref(),
+        COMMON_BASE_SEED.as_bytes(),
+        DailyTokenClaimsDictionary::BASE_SEED.as_bytes(),
+        &[bump],
+    ];
+
+    program::invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            dtc_pda_account.clone(),
+            system_account.clone(),
+        ],
+        &[signature_seeds],
+    )?;
+    Ok(())
+}
+
+fn create_pending_claim_pda_account(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _chain_id: u64,
+    _index: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pending_claim_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_account = next_account_info(account_info_iter)?;
+
+    let (pda, bump) = PendingClaim::generate_pda_key(_program_id, _chain_id, _index);
+    if !pending_claim_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        pending_claim_account.key,
+        rent_sysvar.minimum_balance(PendingClaim::LEN),
+        PendingClaim::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            pending_claim_account.clone(),
+            system_account.clone(),
+        ],
+        &[&[
+            _chain_id.to_le_bytes().as_ref(),
+            _index.to_le_bytes().as_ref(),
+            COMMON_BASE_SEED.as_bytes(),
+            PendingClaim::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+    Ok(())
+}
+
+fn create_recipient_claims_dictionary_pda_account(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _token_index: u64,
+    _recipient: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let recipient_claims_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_account = next_account_info(account_info_iter)?;
+
+    let (pda, bump) =
+        RecipientClaimsDictionary::generate_pda_key(_program_id, _token_index, &_recipient);
+    if !recipient_claims_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        payer.key,
+        recipient_claims_account.key,
+        rent_sysvar.minimum_balance(RecipientClaimsDictionary::LEN),
+        RecipientClaimsDictionary::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            payer.clone(),
+            recipient_claims_account.clone(),
+            system_account.clone(),
+        ],
+        &[&[
+            _token_index.to_le_bytes().as_ref(),
+            _recipient.as_ref(),
+            COMMON_BASE_SEED.as_bytes(),
+            RecipientClaimsDictionary::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    let recipient_claims_data = RecipientClaimsDictionary {
+        is_initialized: true,
+        ..Default::default()
+    };
+    recipient_claims_data.pack_into_slice(&mut recipient_claims_account.data.borrow_mut()[..]);
+    Ok(())
+}
+fn create_validator_set(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _validators: &[Pubkey],
+    _threshold: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let validator_set_account = next_account_info(account_info_iter)?;
+    let rent_sysvar_account = next_account_info(account_info_iter)?;
+    let system_program_account = next_account_info(account_info_iter)?;
+
+    only_owner(_program_id, owner_account, bridge_account)?;
+
+    let (pda, bump) = ValidatorSet::generate_pda_key(_program_id);
+    if !validator_set_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if _validators.len() > MAX_VALIDATORS {
+        return Err(ProgramError::Custom(BridgeError::ValidatorSetFull as u32));
+    }
+    if _threshold == 0 || _threshold as usize > _validators.len() {
+        return Err(ProgramError::Custom(
+            BridgeError::InvalidValidatorThreshold as u32,
+        ));
+    }
+
+    let mut validator_set_data = ValidatorSet {
+        is_initialized: true,
+        threshold: _threshold,
+        ..ValidatorSet::default()
+    };
+    for validator in _validators.iter() {
+        if validator_set_data.is_member(validator) {
+            return Err(ProgramError::Custom(
+                BridgeError::ValidatorAlreadyAdded as u32,
+            ));
+        }
+        validator_set_data.validators[validator_set_data.count as usize] = *validator;
+        validator_set_data.count += 1;
+    }
+
+    let rent_sysvar = Rent::from_account_info(rent_sysvar_account)?;
+    let ix = system_instruction::create_account(
+        owner_account.key,
+        validator_set_account.key,
+        rent_sysvar.minimum_balance(ValidatorSet::LEN),
+        ValidatorSet::LEN.try_into().unwrap(),
+        _program_id,
+    );
+    program::invoke_signed(
+        &ix,
+        &[
+            owner_account.clone(),
+            validator_set_account.clone(),
+            system_program_account.clone(),
+        ],
+        &[&[
+            COMMON_BASE_SEED.as_bytes(),
+            ValidatorSet::BASE_SEED.as_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    validator_set_data.pack_into_slice(&mut validator_set_account.data.borrow_mut()[..]);
+    BridgeEvent::ValidatorSetCreated {
+        validators: _validators.to_vec(),
+        threshold: _threshold,
+    }
+    .emit();
+    Ok(())
+}
+
+fn add_validator(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _validator: Pubkey,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let validator_set_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let mut validator_set_data = unpack_validator_set(_program_id, validator_set_account)?;
+
+    if validator_set_data.is_member(&_validator) {
+        return Err(ProgramError::Custom(
+            BridgeError::ValidatorAlreadyAdded as u32,
+        ));
+    }
+    if validator_set_data.count as usize >= MAX_VALIDATORS {
+        return Err(ProgramError::Custom(BridgeError::ValidatorSetFull as u32));
+    }
+
+    validator_set_data.validators[validator_set_data.count as usize] = _validator;
+    validator_set_data.count += 1;
+    validator_set_data.pack_into_slice(&mut validator_set_account.data.borrow_mut()[..]);
+    BridgeEvent::ValidatorAdded {
+        validator: _validator,
+    }
+    .emit();
+    Ok(())
+}
+
+fn remove_validator(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _validator: Pubkey,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let validator_set_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let mut validator_set_data = unpack_validator_set(_program_id, validator_set_account)?;
+
+    let position = match validator_set_data
+        .members()
+        .iter()
+        .position(|validator| *validator == _validator)
+    {
+        None => {
+            return Err(ProgramError::Custom(
+                BridgeError::NonExistantValidator as u32,
+            ))
+        }
+        Some(position) => position,
+    };
+    // the remaining validators must still be able to reach the threshold
+    if validator_set_data.count <= validator_set_data.threshold {
+        return Err(ProgramError::Custom(
+            BridgeError::InvalidValidatorThreshold as u32,
+        ));
+    }
+
+    // move the last validator into the freed slot
+    let last = validator_set_data.count as usize - 1;
+    validator_set_data.validators[position] = validator_set_data.validators[last];
+    validator_set_data.validators[last] = Pubkey::default();
+    validator_set_data.count -= 1;
+    validator_set_data.pack_into_slice(&mut validator_set_account.data.borrow_mut()[..]);
+    BridgeEvent::ValidatorRemoved {
+        validator: _validator,
+    }
+    .emit();
+    Ok(())
+}
+
+fn update_validator_threshold(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _threshold: u8,
+) -> ProgramResult {
+    verify_program_accounts_ownership(_program_id, _accounts[1..3].as_ref())?;
+    let account_info_iter = &mut _accounts.iter();
+    let _executor_account = next_account_info(account_info_iter)?;
+    let bridge_account = next_account_info(account_info_iter)?;
+    let validator_set_account = next_account_info(account_info_iter)?;
+
+    unpack_bridge(_program_id, bridge_account)?;
+
+    let mut validator_set_data = unpack_validator_set(_program_id, validator_set_account)?;
+
+    if _threshold == 0 || _threshold > validator_set_data.count {
+        return Err(ProgramError::Custom(
+            BridgeError::InvalidValidatorThreshold as u32,
+        ));
+    }
+
+    validator_set_data.threshold = _threshold;
+    validator_set_data.pack_into_slice(&mut validator_set_account.data.borrow_mut()[..]);
+    BridgeEvent::ValidatorThresholdUpdated {
+        threshold: _threshold,
+    }
+    .emit();
+    Ok(())
+}
+// ========================== Helper Functions ==================== //
+
+// Checks that all provided accounts are owned by the provided program_id
+fn verify_program_accounts_ownership(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+) -> ProgramResult {
+    // verify all accounts are owned by current program
+    for account in _accounts.into_iter() {
+        if account.owner != _program_id {
+            return Err(ProgramError::Custom(
+                BridgeError::AccountNotOwnedByBridge as u32,
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Verifies that Bridge account owner initiated the transaction and returns the Bridge data
+fn only_owner(
+    _program_id: &Pubkey,
+    _owner_account: &AccountInfo,
+    bridge_account: &AccountInfo,
+) -> Result<Bridge, ProgramError> {
+    if !_owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let bridge_data = unpack_bridge(_program_id, bridge_account)?;
+    if bridge_data.owner != *_owner_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(bridge_data)
+}
+
+// Verifies that an account holding 'role' initiated the transaction
+fn only_role(
+    _program_id: &Pubkey,
+    _member_account: &AccountInfo,
+    role_account: &AccountInfo,
+    role: Role,
+) -> ProgramResult {
+    if !_member_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (pda, _) = RoleDictionary::generate_pda_key(_program_id, role, _member_account.key);
+    if !role_account.key.eq(&pda) || role_account.owner != _program_id {
+        return Err(ProgramError::Custom(BridgeError::MissingRole as u32));
+    }
+    let role_data = RoleDictionary::unpack_from_slice(&role_account.data.borrow())?;
+    if !role_data.has_role {
+        return Err(ProgramError::Custom(BridgeError::MissingRole as u32));
+    }
+    Ok(())
+}
+
+// Role allowed to queue a timelocked instruction, None when only the owner can
+fn timelock_role(instruction: &BridgeInstruction) -> Result<Option<Role>, ProgramError> {
+    match instruction {
+        BridgeInstruction::SetLamportFeeOnlyOwner { .. }
+        | BridgeInstruction::SetFeeRoundingOnlyOwner { .. } => Ok(Some(Role::FeeManager)),
+        BridgeInstruction::UpdateTokenLimitOnlyOwner { .. }
+        | BridgeInstruction::SetTokenLimitWindowOnlyOwner { .. }
+        | BridgeInstruction::SetOutboundLimitOnlyOwner { .. }
+        | BridgeInstruction::UpdateRecipientLimitOnlyOwner { .. }
+        | BridgeInstruction::SetSettlementOnlyOwner { .. } => Ok(Some(Role::LimitManager)),
+        BridgeInstruction::AddTokenOnlyOwner { .. }
+        | BridgeInstruction::SetTokenMintBurnOnlyOwner { .. }
+        | BridgeInstruction::SetRemoteDecimalsOnlyOwner { .. }
+        | BridgeInstruction::SetRemoteTokenOnlyOwner { .. } => Ok(Some(Role::TokenLister)),
+        BridgeInstruction::AddValidatorOnlyOwner { .. }
+        | BridgeInstruction::RemoveValidatorOnlyOwner { .. }
+        | BridgeInstruction::UpdateValidatorThresholdOnlyOwner { .. }
+        | BridgeInstruction::AddChainOnlyOwner { .. }
+        | BridgeInstruction::UpdateChainOnlyOwner { .. }
+        | BridgeInstruction::GrantRoleOnlyOwner { .. }
+        | BridgeInstruction::UpdateTimelockDelayOnlyOwner { .. }
+        | BridgeInstruction::SetTreasuryOnlyOwner { .. } => Ok(None),
+        _ => Err(ProgramError::Custom(
+            BridgeError::ActionNotTimelocked as u32,
+        )),
+    }
+}
+
+// Fails while the whole Bridge is paused
+fn when_not_paused(bridge_data: &Bridge) -> ProgramResult {
+    if bridge_data.paused {
+        return Err(ProgramError::Custom(BridgeError::BridgePaused as u32));
+    }
+    Ok(())
+}
+
+// Checks the Bridge PDA key and owner and returns its data. Any other account owned by the
+// program could otherwise be passed, and read or overwritten, as the Bridge.
+fn unpack_bridge(
+    _program_id: &Pubkey,
+    bridge_account: &AccountInfo,
+) -> Result<Bridge, ProgramError> {
+    let (pda, _, _, _) = Bridge::generate_pda_key(_program_id);
+    if !bridge_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if bridge_account.owner != _program_id {
+        return Err(ProgramError::Custom(
+            BridgeError::AccountNotOwnedByBridge as u32,
+        ));
+    }
+    let bridge_data = Bridge::unpack_from_slice(&bridge_account.data.borrow())?;
+    if !bridge_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(bridge_data)
+}
+
+// Checks the PendingClaim PDA key and returns its data if it is still pending
+fn unpack_pending_claim(
+    _program_id: &Pubkey,
+    pending_claim_account: &AccountInfo,
+    _chain_id: u64,
+    _index: u64,
+) -> Result<PendingClaim, ProgramError> {
+    let (pda, _) = PendingClaim::generate_pda_key(_program_id, _chain_id, _index);
+    if !pending_claim_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let pending_claim_data = PendingClaim::unpack_from_slice(&pending_claim_account.data.borrow())?;
+    if !pending_claim_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if pending_claim_data.executed || pending_claim_data.cancelled {
+        return Err(ProgramError::Custom(
+            BridgeError::PendingClaimAlreadyDone as u32,
+        ));
+    }
+    Ok(pending_claim_data)
+}
+
+// Fails unless 'threshold' distinct validators signed 'message' in Ed25519 instructions
+// preceding the current one
+fn check_validator_signatures(
+    validator_set_data: &ValidatorSet,
+    instructions_sysvar_account: &AccountInfo,
+    message: &[u8],
+) -> ProgramResult {
+    let signers = verified_signers(instructions_sysvar_account, message)?;
+    let approvals = validator_set_data
+        .members()
+        .iter()
+        .filter(|validator| signers.contains(validator))
+        .count();
+    if validator_set_data.threshold == 0 || approvals < validator_set_data.threshold as usize {
+        return Err(ProgramError::Custom(
+            BridgeError::InsufficientValidatorSignatures as u32,
+        ));
+    }
+    Ok(())
+}
+
+// Decimals of a mint of either token program, extensions included
+fn mint_decimals(mint_account: &AccountInfo) -> Result<u8, ProgramError> {
+    let mint_data = mint_account.data.borrow();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
+
+// Size of a token account of the mint, with the extensions its mint requires
+fn token_account_len(mint_account: &AccountInfo) -> Result<usize, ProgramError> {
+    let mint_data = mint_account.data.borrow();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let account_extensions =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&account_extensions)
+}
+
+// Unpacks a token account of either token program, extensions included
+fn unpack_token_account(
+    token_account: &AccountInfo,
+) -> Result<spl_token_2022::state::Account, ProgramError> {
+    let token_account_data = token_account.data.borrow();
+    let account =
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&token_account_data)?;
+    Ok(account.base)
+}
+
+// Checks the ValidatorSet PDA key and returns its initialized data
+fn unpack_validator_set(
+    _program_id: &Pubkey,
+    validator_set_account: &AccountInfo,
+) -> Result<ValidatorSet, ProgramError> {
+    let (pda, _) = ValidatorSet::generate_pda_key(_program_id);
+    if !validator_set_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let validator_set_data = ValidatorSet::unpack_from_slice(&validator_set_account.data.borrow())?;
+    if !validator_set_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(validator_set_data)
+}
+
+// Checks the OwnerMultisig PDA key and returns its initialized data
+fn unpack_owner_multisig(
+    _program_id: &Pubkey,
+    owner_multisig_account: &AccountInfo,
+) -> Result<OwnerMultisig, ProgramError> {
+    let (pda, _) = OwnerMultisig::generate_pda_key(_program_id);
+    if !owner_multisig_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let owner_multisig_data =
+        OwnerMultisig::unpack_from_slice(&owner_multisig_account.data.borrow())?;
+    if !owner_multisig_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(owner_multisig_data)
+}
+
+// Checks the MultisigTransaction PDA key and that it can still be approved or executed
+fn unpack_multisig_transaction(
+    _program_id: &Pubkey,
+    multisig_transaction_account: &AccountInfo,
+    _transaction_id: u64,
+    owner_multisig_data: &OwnerMultisig,
+) -> Result<MultisigTransaction, ProgramError> {
+    let (pda, _) = MultisigTransaction::generate_pda_key(_program_id, _transaction_id);
+    if !multisig_transaction_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let multisig_transaction_data =
+        MultisigTransaction::unpack_from_slice(&multisig_transaction_account.data.borrow())?;
+    if !multisig_transaction_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if multisig_transaction_data.executed {
+        return Err(ProgramError::Custom(
+            BridgeError::TransactionAlreadyExecuted as u32,
+        ));
+    }
+    if multisig_transaction_data.signers_seqno != owner_multisig_data.signers_seqno {
+        return Err(ProgramError::Custom(
+            BridgeError::MultisigSignersChanged as u32,
+        ));
+    }
+    Ok(multisig_transaction_data)
+}
+
+// Returns the index of the signing account among the multisig signers
+fn multisig_signer_position(
+    signer_account: &AccountInfo,
+    owner_multisig_data: &OwnerMultisig,
+) -> Result<usize, ProgramError> {
+    if !signer_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    owner_multisig_data
+        .members()
+        .iter()
+        .position(|signer| signer == signer_account.key)
+        .ok_or(ProgramError::Custom(BridgeError::NotMultisigSigner as u32))
+}
+
+// Replaces the signers of the multisig after checking them against the threshold
+fn set_multisig_signers(
+    owner_multisig_data: &mut OwnerMultisig,
+    _signers: &[Pubkey],
+    _threshold: u8,
+) -> ProgramResult {
+    if _signers.len() > MAX_MULTISIG_SIGNERS
+        || _threshold == 0
+        || _threshold as usize > _signers.len()
+    {
+        return Err(ProgramError::Custom(
+            BridgeError::InvalidMultisigThreshold as u32,
+        ));
+    }
+    for (i, signer) in _signers.iter().enumerate() {
+        if _signers[..i].contains(signer) {
+            return Err(ProgramError::Custom(
+                BridgeError::InvalidMultisigThreshold as u32,
+            ));
+        }
+    }
+
+    owner_multisig_data.signers = [Pubkey::default(); MAX_MULTISIG_SIGNERS];
+    owner_multisig_data.signers[.._signers.len()].copy_from_slice(_signers);
+    owner_multisig_data.count = _signers.len() as u8;
+    owner_multisig_data.threshold = _threshold;
+    Ok(())
+}
+
+// Checks the ChainRegistry PDA key and that the chain is enabled and not paused
+fn unpack_chain_registry(
+    _program_id: &Pubkey,
+    chain_registry_account: &AccountInfo,
+    _chain_id: u64,
+) -> Result<ChainRegistry, ProgramError> {
+    if chain_registry_account.owner != _program_id {
+        return Err(ProgramError::Custom(BridgeError::ChainNotRegistered as u32));
+    }
+    let (pda, _) = ChainRegistry::generate_pda_key(_program_id, _chain_id);
+    if !chain_registry_account.key.eq(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let chain_registry_data =
+        ChainRegistry::unpack_from_slice(&chain_registry_account.data.borrow())?;
+    if !chain_registry_data.enabled {
+        return Err(ProgramError::Custom(BridgeError::ChainNotRegistered as u32));
+    }
+    if chain_registry_data.paused {
+        return Err(ProgramError::Custom(BridgeError::ChainPaused as u32));
+    }
+    Ok(chain_registry_data)
+}
+
+fn generate_bridge_token_pda(_program_id: &Pubkey, mint: &Pubkey) -> (Pubkey, u8, String, String) {
+    let seed1 = "bridge";
+    let seed2 = "bridge_token_account";
+    let seeds = &[
+        mint.as_ref(),
+        seed1.as_bytes().as_ref(),
+        seed2.as_bytes().as_ref(),
+    ];
+    let (pda, bump) = Pubkey::find_program_address(seeds, _program_id);
     return (pda, bump, seed1.to_string(), seed2.to_string());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::tests::{ed25519_instruction_data, instructions_sysvar_data};
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use solana_program::program_utils::limited_deserialize;
+    use solana_program::system_instruction::SystemInstruction;
+    use solana_program::{ed25519_program, entrypoint::SUCCESS, system_program, sysvar};
+    use solana_sdk::packet::PACKET_DATA_SIZE;
+    use spl_token_2022::extension::transfer_fee::{
+        TransferFee, TransferFeeAmount, TransferFeeConfig,
+    };
+    use spl_token_2022::extension::StateWithExtensionsMut;
+    use std::sync::Once;
+
+    // unix time every handler reads from the Clock sysvar
+    const NOW: i64 = 1_700_000_000;
+
+    struct TestSyscalls;
+
+    impl SyscallStubs for TestSyscalls {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock {
+                unix_timestamp: NOW,
+                ..Clock::default()
+            };
+            unsafe { (var_addr as *mut Clock).write(clock) };
+            SUCCESS
+        }
+
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { (var_addr as *mut Rent).write(Rent::default()) };
+            SUCCESS
+        }
+
+        // the token programs run for real over the accounts passed along, other programs
+        // are no-ops and the test sets up what they would have done
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let accounts = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    let mut account = account_infos
+                        .iter()
+                        .find(|account| *account.key == meta.pubkey)
+                        .ok_or(ProgramError::NotEnoughAccountKeys)?
+                        .clone();
+                    // like the runtime, a key signs if any of its entries does
+                    account.is_signer = instruction
+                        .accounts
+                        .iter()
+                        .any(|other| other.pubkey == meta.pubkey && other.is_signer);
+                    Ok(account)
+                })
+                .collect::<Result<Vec<_>, ProgramError>>()?;
+            if instruction.program_id == spl_token::id() {
+                spl_token::processor::Processor::process(
+                    &instruction.program_id,
+                    &accounts,
+                    &instruction.data,
+                )
+            } else if instruction.program_id == spl_token_2022::id() {
+                spl_token_2022::processor::Processor::process(
+                    &instruction.program_id,
+                    &accounts,
+                    &instruction.data,
+                )
+            } else if instruction.program_id == system_program::id() {
+                // transfers move lamports, an account is created up front at the size
+                // and owner requested
+                match limited_deserialize(&instruction.data, PACKET_DATA_SIZE as u64) {
+                    Ok(SystemInstruction::CreateAccount { space, owner, .. })
+                        if accounts[1].data_len() as u64 != space
+                            || *accounts[1].owner != owner =>
+                    {
+                        Err(ProgramError::InvalidAccountData)
+                    }
+                    Ok(SystemInstruction::Transfer { lamports }) => {
+                        let mut from_lamports = accounts[0].try_borrow_mut_lamports()?;
+                        **from_lamports = from_lamports
+                            .checked_sub(lamports)
+                            .ok_or(ProgramError::InsufficientFunds)?;
+                        **accounts[1].try_borrow_mut_lamports()? += lamports;
+                        Ok(())
+                    }
+                    _ => Ok(()),
+                }
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    // installed before any handler runs, see TestAccount::info
+    fn install_syscall_stubs() {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(TestSyscalls));
+        });
+    }
+
+    // owned storage an AccountInfo borrows from
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        is_signer: bool,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, len: usize) -> Self {
+            TestAccount {
+                key,
+                owner,
+                is_signer: false,
+                lamports: 1_000_000_000,
+                data: vec![0; len],
+            }
+        }
+
+        fn signer(key: Pubkey) -> Self {
+            TestAccount {
+                is_signer: true,
+                ..TestAccount::new(key, system_program::id(), 0)
+            }
+        }
+
+        fn packed<T: Pack>(key: Pubkey, owner: Pubkey, value: &T) -> Self {
+            let mut account = TestAccount::new(key, owner, T::LEN);
+            value.pack_into_slice(&mut account.data);
+            account
+        }
+
+        fn rent_sysvar() -> Self {
+            let mut account = TestAccount::new(sysvar::rent::id(), sysvar::id(), Rent::size_of());
+            Rent::default()
+                .to_account_info(&mut account.info())
+                .unwrap();
+            account
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            install_syscall_stubs();
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                false,
+                0,
+            )
+        }
+
+        fn unpack<T: Pack>(&self) -> T {
+            T::unpack_from_slice(&self.data).unwrap()
+        }
+    }
+
+    // runs 'handler' over AccountInfos of 'accounts', which hold the result afterwards
+    fn run(
+        accounts: &mut [TestAccount],
+        handler: impl FnOnce(&[AccountInfo]) -> ProgramResult,
+    ) -> ProgramResult {
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        handler(&infos)
+    }
+
+    fn custom(error: BridgeError) -> ProgramError {
+        ProgramError::Custom(error as u32)
+    }
+
+    // SPL Token mint with 6 decimals
+    fn mint_account(mint: Pubkey) -> TestAccount {
+        let mint_data = spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..spl_token::state::Mint::default()
+        };
+        TestAccount::packed(mint, spl_token::id(), &mint_data)
+    }
+
+    // SPL Token account of 'owner' holding 'amount' of 'mint'
+    fn token_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+        let token_account_data = spl_token::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        };
+        TestAccount::packed(key, spl_token::id(), &token_account_data)
+    }
+
+    fn bridge_account(program_id: &Pubkey, owner: &Pubkey) -> TestAccount {
+        let (pda, _, _, _) = Bridge::generate_pda_key(program_id);
+        let bridge_data = Bridge {
+            is_initialized: true,
+            owner: *owner,
+            ..Bridge::default()
+        };
+        TestAccount::packed(pda, *program_id, &bridge_data)
+    }
+
+    fn guarded_bridge_account(
+        program_id: &Pubkey,
+        owner: &Pubkey,
+        guardian: &Pubkey,
+    ) -> TestAccount {
+        let mut bridge = bridge_account(program_id, owner);
+        let mut bridge_data = bridge.unpack::<Bridge>();
+        bridge_data.guardian = *guardian;
+        bridge_data.pack_into_slice(&mut bridge.data);
+        bridge
+    }
+
+    // an initialized account owned by the program, but not at the Bridge PDA, that parses as one
+    fn fake_bridge_account(program_id: &Pubkey, owner: &Pubkey) -> TestAccount {
+        TestAccount {
+            key: Pubkey::new_unique(),
+            ..bridge_account(program_id, owner)
+        }
+    }
+
+    // empty accounts owned by the program, for the ones a handler doesn't reach
+    fn program_accounts(program_id: &Pubkey, count: usize) -> Vec<TestAccount> {
+        (0..count)
+            .map(|_| TestAccount::new(Pubkey::new_unique(), *program_id, 0))
+            .collect()
+    }
+
+    // token 0, listed and unpaused
+    fn listed_token_account(program_id: &Pubkey) -> TestAccount {
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(program_id, 0);
+        let token_list_data = TokenListDictionary {
+            is_initialized: true,
+            exists: true,
+            ..TokenListDictionary::default()
+        };
+        TestAccount::packed(token_list_pda, *program_id, &token_list_data)
+    }
+
+    fn chain_registry_account(program_id: &Pubkey, chain_id: u64) -> TestAccount {
+        let (pda, _) = ChainRegistry::generate_pda_key(program_id, chain_id);
+        let chain_registry_data = ChainRegistry {
+            is_initialized: true,
+            chain_id,
+            enabled: true,
+            address_kinds: RemoteAddress::ALL_KINDS,
+            ..ChainRegistry::default()
+        };
+        TestAccount::packed(pda, *program_id, &chain_registry_data)
+    }
+
+    fn validator_set_account(program_id: &Pubkey, validators: &[Pubkey]) -> TestAccount {
+        let (validator_set_pda, _) = ValidatorSet::generate_pda_key(program_id);
+        let validator_set_data = validator_set(validators, 1);
+        TestAccount::packed(validator_set_pda, *program_id, &validator_set_data)
+    }
+
+    // an AdminAction of 'proposer' that can be executed now
+    fn ready_admin_action(
+        program_id: &Pubkey,
+        action_id: u64,
+        proposer: &Pubkey,
+        instruction: BridgeInstruction,
+    ) -> TestAccount {
+        let (pda, _) = AdminAction::generate_pda_key(program_id, action_id);
+        let admin_action_data = AdminAction {
+            is_initialized: true,
+            proposer: *proposer,
+            eta: NOW as u64,
+            instruction: instruction.try_to_vec().unwrap(),
+            ..AdminAction::default()
+        };
+        TestAccount::packed(pda, *program_id, &admin_action_data)
+    }
+
+    // builds the accounts of a handler around the attacker's Bridge
+    type BridgeAccounts = fn(&Pubkey, &Pubkey, TestAccount) -> Vec<TestAccount>;
+    // runs the handler as the attacker
+    type BridgeHandler = fn(&Pubkey, &[AccountInfo], &Pubkey) -> ProgramResult;
+
+    #[test]
+    fn handlers_reject_a_bridge_outside_the_pda() {
+        let handlers: &[(&str, BridgeAccounts, BridgeHandler)] = &[
+            (
+                "only_owner",
+                |_, attacker, bridge| vec![TestAccount::signer(*attacker), bridge],
+                |program_id, infos, _| only_owner(program_id, &infos[0], &infos[1]).map(|_| ()),
+            ),
+            (
+                "create_owner_multisig",
+                |program_id, attacker, bridge| {
+                    let (owner_multisig_pda, _) = OwnerMultisig::generate_pda_key(program_id);
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        TestAccount::new(owner_multisig_pda, *program_id, OwnerMultisig::LEN),
+                        TestAccount::rent_sysvar(),
+                        TestAccount::new(system_program::id(), Pubkey::default(), 0),
+                    ]
+                },
+                |program_id, infos, attacker| {
+                    create_owner_multisig(program_id, infos, &[*attacker], 1)
+                },
+            ),
+            (
+                "pause_bridge",
+                |_, attacker, bridge| vec![TestAccount::signer(*attacker), bridge],
+                |program_id, infos, _| pause_bridge(program_id, infos),
+            ),
+            (
+                "unpause_bridge",
+                |_, attacker, bridge| vec![TestAccount::signer(*attacker), bridge],
+                |program_id, infos, _| unpause_bridge(program_id, infos),
+            ),
+            (
+                "set_guardian",
+                |_, attacker, bridge| vec![TestAccount::signer(*attacker), bridge],
+                |program_id, infos, attacker| set_guardian(program_id, infos, *attacker),
+            ),
+            (
+                "grant_role",
+                |program_id, attacker, bridge| {
+                    let (role_pda, _) =
+                        RoleDictionary::generate_pda_key(program_id, Role::Treasurer, attacker);
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        TestAccount::new(role_pda, *program_id, RoleDictionary::LEN),
+                        TestAccount::rent_sysvar(),
+                        TestAccount::new(system_program::id(), Pubkey::default(), 0),
+                    ]
+                },
+                |program_id, infos, attacker| {
+                    grant_role(program_id, infos, Role::Treasurer, *attacker)
+                },
+            ),
+            (
+                "revoke_role",
+                |program_id, attacker, bridge| {
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        role_account(program_id, Role::Treasurer, attacker, true),
+                    ]
+                },
+                |program_id, infos, attacker| {
+                    revoke_role(program_id, infos, Role::Treasurer, *attacker)
+                },
+            ),
+            (
+                // a Treasurer can't redirect withdrawals through a Bridge naming another treasury
+                "withdraw_lamport_fees",
+                |program_id, attacker, mut bridge| {
+                    let mut bridge_data = bridge.unpack::<Bridge>();
+                    bridge_data.treasury = *attacker;
+                    bridge_data.pack_into_slice(&mut bridge.data);
+                    let (fee_vault_pda, _) = FeeVault::generate_pda_key(program_id);
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        TestAccount::new(fee_vault_pda, *program_id, FeeVault::LEN),
+                        TestAccount::new(*attacker, system_program::id(), 0),
+                        role_account(program_id, Role::Treasurer, attacker, true),
+                    ]
+                },
+                |program_id, infos, _| withdraw_lamport_fees(program_id, infos, 1),
+            ),
+            (
+                "withdraw_fees",
+                |program_id, attacker, bridge| {
+                    [
+                        vec![TestAccount::signer(*attacker)],
+                        program_accounts(program_id, 1),
+                        vec![bridge],
+                        program_accounts(program_id, 3),
+                        vec![role_account(program_id, Role::Treasurer, attacker, true)],
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+                },
+                |program_id, infos, _| withdraw_fees(program_id, infos, 0, 1),
+            ),
+            (
+                "cancel_pending_claim",
+                |program_id, attacker, bridge| {
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        pending_claim_account(program_id, NOW as u64 + 1),
+                    ]
+                },
+                |program_id, infos, _| cancel_pending_claim(program_id, infos, 1, 0),
+            ),
+            (
+                "execute_pending_claim",
+                |program_id, _, bridge| {
+                    vec![
+                        bridge,
+                        pending_claim_account(program_id, NOW as u64),
+                        listed_token_account(program_id),
+                    ]
+                },
+                |program_id, infos, _| execute_pending_claim(program_id, infos, 1, 0),
+            ),
+            (
+                "transfer_request",
+                |program_id, attacker, bridge| {
+                    [
+                        vec![bridge, listed_token_account(program_id)],
+                        program_accounts(program_id, 3),
+                        vec![TestAccount::signer(*attacker)],
+                        program_accounts(program_id, 7),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+                },
+                |program_id, infos, _| {
+                    transfer_request(program_id, infos, 0, RemoteAddress::default(), 1, 2)
+                },
+            ),
+            (
+                "transfer_receipt",
+                |program_id, attacker, bridge| {
+                    let (claimed_pda, _) = ClaimedDictionary::generate_pda_key(program_id, 0, 0);
+                    let (daily_token_claims_pda, _) =
+                        DailyTokenClaimsDictionary::generate_pda_key(program_id, 0);
+                    [
+                        vec![
+                            bridge,
+                            validator_set_account(program_id, &[*attacker]),
+                            TestAccount::new(Pubkey::new_unique(), *program_id, 0),
+                            TestAccount::new(claimed_pda, *program_id, ClaimedDictionary::LEN),
+                            listed_token_account(program_id),
+                            TestAccount::new(
+                                daily_token_claims_pda,
+                                *program_id,
+                                DailyTokenClaimsDictionary::LEN,
+                            ),
+                        ],
+                        program_accounts(program_id, 3),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+                },
+                |program_id, infos, _| transfer_receipt(program_id, infos, &Receipt::default()),
+            ),
+            (
+                "refund_request",
+                |program_id, attacker, bridge| {
+                    [
+                        vec![bridge, validator_set_account(program_id, &[*attacker])],
+                        program_accounts(program_id, 7),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+                },
+                |program_id, infos, _| refund_request(program_id, infos, 0, true),
+            ),
+            (
+                "quote_fee",
+                |program_id, _, bridge| vec![bridge, listed_token_account(program_id)],
+                |program_id, infos, _| quote_fee(program_id, infos, 0, 1).map(|_| ()),
+            ),
+            (
+                "queue_admin_action",
+                |program_id, attacker, bridge| {
+                    let (admin_action_pda, _) = AdminAction::generate_pda_key(program_id, 0);
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        TestAccount::new(admin_action_pda, *program_id, AdminAction::LEN),
+                        TestAccount::rent_sysvar(),
+                        TestAccount::new(system_program::id(), Pubkey::default(), 0),
+                    ]
+                },
+                |program_id, infos, attacker| {
+                    let instruction = BridgeInstruction::GrantRoleOnlyOwner {
+                        role: Role::Treasurer,
+                        account: *attacker,
+                    };
+                    queue_admin_action(program_id, infos, instruction.try_to_vec().unwrap())
+                },
+            ),
+            (
+                "cancel_admin_action",
+                |program_id, attacker, bridge| {
+                    let instruction =
+                        BridgeInstruction::UpdateTimelockDelayOnlyOwner { timelock_delay: 0 };
+                    vec![
+                        TestAccount::signer(*attacker),
+                        bridge,
+                        ready_admin_action(program_id, 0, attacker, instruction),
+                    ]
+                },
+                |program_id, infos, _| cancel_admin_action(program_id, infos, 0),
+            ),
+            (
+                // anyone can execute a ready action, so the accounts it runs on must be checked
+                "execute_admin_action(UpdateTimelockDelayOnlyOwner)",
+                |program_id, attacker, bridge| {
+                    let instruction =
+                        BridgeInstruction::UpdateTimelockDelayOnlyOwner { timelock_delay: 0 };
+                    vec![
+                        ready_admin_action(program_id, 0, attacker, instruction),
+                        TestAccount::signer(*attacker),
+                        bridge,
+                    ]
+                },
+                |program_id, infos, _| execute_admin_action(program_id, infos, 0),
+            ),
+            (
+                "execute_admin_action(SetTreasuryOnlyOwner)",
+                |program_id, attacker, bridge| {
+                    let instruction = BridgeInstruction::SetTreasuryOnlyOwner {
+                        treasury: *attacker,
+                    };
+                    vec![
+                        ready_admin_action(program_id, 0, attacker, instruction),
+                        TestAccount::signer(*attacker),
+                        bridge,
+                    ]
+                },
+                |program_id, infos, _| execute_admin_action(program_id, infos, 0),
+            ),
+        ];
+
+        for (name, accounts, handler) in handlers {
+            let program_id = Pubkey::new_unique();
+            let attacker = Pubkey::new_unique();
+            let bridge = fake_bridge_account(&program_id, &attacker);
+            let bridge_key = bridge.key;
+            let mut accounts = accounts(&program_id, &attacker, bridge);
+            let bridge_data = |accounts: &[TestAccount]| {
+                let bridge = accounts.iter().find(|account| account.key == bridge_key);
+                bridge.unwrap().data.clone()
+            };
+            let before = bridge_data(&accounts);
+            let result = run(&mut accounts, |infos| {
+                handler(&program_id, infos, &attacker)
+            });
+            assert_eq!(result, Err(ProgramError::InvalidSeeds), "{name}");
+            assert_eq!(bridge_data(&accounts), before, "{name}");
+        }
+    }
+
+    // -------------------------------- Tokens ------------------------------- //
+
+    // Token-2022 mint with 6 decimals, withholding 'basis_points' of every transfer
+    fn token_2022_mint_account(mint: Pubkey, basis_points: u16) -> TestAccount {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        let mut account = TestAccount::new(mint, spl_token_2022::id(), len);
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(
+                &mut account.data,
+            )
+            .unwrap();
+        let transfer_fee = TransferFee {
+            epoch: 0.into(),
+            maximum_fee: u64::MAX.into(),
+            transfer_fee_basis_points: basis_points.into(),
+        };
+        let transfer_fee_config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+        transfer_fee_config.older_transfer_fee = transfer_fee;
+        transfer_fee_config.newer_transfer_fee = transfer_fee;
+        state.base = spl_token_2022::state::Mint {
+            supply: 1_000_000,
+            decimals: 6,
+            is_initialized: true,
+            ..spl_token_2022::state::Mint::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        account
+    }
+
+    // Token-2022 account of 'owner' holding 'amount' of a mint with a transfer fee
+    fn token_2022_account(key: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) -> TestAccount {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&[
+            ExtensionType::TransferFeeAmount,
+        ])
+        .unwrap();
+        let mut account = TestAccount::new(key, spl_token_2022::id(), len);
+        let mut state =
+            StateWithExtensionsMut::<spl_token_2022::state::Account>::unpack_uninitialized(
+                &mut account.data,
+            )
+            .unwrap();
+        state.init_extension::<TransferFeeAmount>(true).unwrap();
+        state.base = spl_token_2022::state::Account {
+            mint,
+            owner,
+            amount,
+            state: spl_token_2022::state::AccountState::Initialized,
+            ..spl_token_2022::state::Account::default()
+        };
+        state.pack_base();
+        state.init_account_type().unwrap();
+        account
+    }
+
+    fn token_amount(account: &TestAccount) -> u64 {
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .unwrap()
+            .base
+            .amount
+    }
+
+    // TransferRequest accounts for token 0 to chain 2, with the Bridge at 'current_index' 0
+    fn transfer_request_accounts(
+        program_id: &Pubkey,
+        sender: &Pubkey,
+        bridge: TestAccount,
+        token_list: TestAccount,
+        mint: TestAccount,
+        source_token: TestAccount,
+        bridge_token: TestAccount,
+    ) -> Vec<TestAccount> {
+        let (record_pda, _) = TransferRequestRecord::generate_pda_key(program_id, 0);
+        let (outbound_limit_pda, _) = OutboundLimitDictionary::generate_pda_key(program_id, 0);
+        let (remote_token_pda, _) = RemoteTokenMapping::generate_pda_key(program_id, 0, 2);
+        let (fee_vault_pda, _) = FeeVault::generate_pda_key(program_id);
+        let (fee_schedule_pda, _) = FeeSchedule::generate_pda_key(program_id, 0);
+        vec![
+            bridge,
+            token_list,
+            mint,
+            source_token,
+            bridge_token,
+            TestAccount::signer(*sender),
+            TestAccount::new(record_pda, *program_id, TransferRequestRecord::LEN),
+            TestAccount::new(system_program::id(), Pubkey::default(), 0),
+            chain_registry_account(program_id, 2),
+            TestAccount::new(outbound_limit_pda, *program_id, 0),
+            TestAccount::new(remote_token_pda, *program_id, 0),
+            TestAccount::new(fee_vault_pda, *program_id, 0),
+            TestAccount::new(fee_schedule_pda, *program_id, 0),
+        ]
+    }
+
+    #[test]
+    fn listed_token_2022_mint_locks_into_its_bridge_token_account() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let (mint, sender) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+        let (token_added_pda, _, _, _) = TokenAddedDictionary::generate_pda_key(&program_id, &mint);
+        let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(&program_id, &mint);
+        // the System program creates it at the size add_token asks for
+        let bridge_token_len = ExtensionType::try_calculate_account_len::<
+            spl_token_2022::state::Account,
+        >(&[ExtensionType::TransferFeeAmount])
+        .unwrap();
+        let mut accounts = [
+            TestAccount::signer(owner),
+            bridge_account(&program_id, &owner),
+            TestAccount::new(token_list_pda, program_id, TokenListDictionary::LEN),
+            TestAccount::new(token_added_pda, program_id, TokenAddedDictionary::LEN),
+            token_2022_mint_account(mint, 100),
+            TestAccount::rent_sysvar(),
+            TestAccount::new(system_program::id(), Pubkey::default(), 0),
+            TestAccount::new(bridge_token_pda, spl_token_2022::id(), bridge_token_len),
+            TestAccount::new(spl_token_2022::id(), Pubkey::default(), 0),
+        ];
+        let result = run(&mut accounts, |infos| {
+            add_token(&program_id, infos, 0, mint, 0, 0)
+        });
+        assert_eq!(result, Ok(()));
+
+        let [_, bridge, token_list, _, mint_account, _, _, bridge_token, _] = accounts;
+        assert_eq!(
+            token_list.unpack::<TokenListDictionary>().token_program,
+            spl_token_2022::id()
+        );
+        let bridge_token_data =
+            StateWithExtensions::<spl_token_2022::state::Account>::unpack(&bridge_token.data)
+                .unwrap();
+        assert_eq!(bridge_token_data.base.mint, mint);
+        assert_eq!(bridge_token_data.base.owner, bridge.key);
+        assert!(bridge_token_data
+            .get_extension::<TransferFeeAmount>()
+            .is_ok());
+
+        // the mint withholds 1% of the locked amount
+        let source_token = token_2022_account(Pubkey::new_unique(), mint, sender, 1_000);
+        let mut accounts = transfer_request_accounts(
+            &program_id,
+            &sender,
+            bridge,
+            token_list,
+            mint_account,
+            source_token,
+            bridge_token,
+        );
+        let result = run(&mut accounts, |infos| {
+            transfer_request(
+                &program_id,
+                infos,
+                0,
+                RemoteAddress::Ed25519([1; 32]),
+                1_000,
+                2,
+            )
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(token_amount(&accounts[3]), 0);
+        assert_eq!(token_amount(&accounts[4]), 990);
+        assert_eq!(accounts[6].unpack::<TransferRequestRecord>().amount, 990);
+    }
+
+    // requests 10_000 of token 0, a Token-2022 mint withholding 1% of every transfer, with a
+    // Bridge fee of 1%
+    fn request_with_transfer_fee(mint_burn: bool) -> (ProgramResult, Vec<TestAccount>) {
+        let program_id = Pubkey::new_unique();
+        let (mint, sender) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bridge = bridge_account(&program_id, &Pubkey::new_unique());
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+        let token_list_data = TokenListDictionary {
+            is_initialized: true,
+            token_address: mint,
+            exists: true,
+            fee: TEN_POW_18 / 100,
+            mint_burn,
+            token_program: spl_token_2022::id(),
+            local_decimals: 6,
+            remote_decimals: 6,
+            ..TokenListDictionary::default()
+        };
+        let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(&program_id, &mint);
+        let bridge_token = token_2022_account(bridge_token_pda, mint, bridge.key, 0);
+        let mut accounts = transfer_request_accounts(
+            &program_id,
+            &sender,
+            bridge,
+            TestAccount::packed(token_list_pda, program_id, &token_list_data),
+            token_2022_mint_account(mint, 100),
+            token_2022_account(Pubkey::new_unique(), mint, sender, 10_000),
+            bridge_token,
+        );
+        let result = run(&mut accounts, |infos| {
+            transfer_request(
+                &program_id,
+                infos,
+                0,
+                RemoteAddress::Ed25519([1; 32]),
+                10_000,
+                2,
+            )
+        });
+        (result, accounts)
+    }
+
+    #[test]
+    fn locked_tokens_are_bridged_as_received_after_the_transfer_fee() {
+        let (result, accounts) = request_with_transfer_fee(false);
+        assert_eq!(result, Ok(()));
+        // the mint withholds 100 of the 10_000 locked, the Bridge's fee is 1% of the rest
+        assert_eq!(token_amount(&accounts[3]), 0);
+        assert_eq!(token_amount(&accounts[4]), 9_900);
+        let record_data = accounts[6].unpack::<TransferRequestRecord>();
+        assert_eq!(record_data.amount, 9_900);
+        assert_eq!(record_data.fee, 99);
+        let token_list_data = accounts[1].unpack::<TokenListDictionary>();
+        assert_eq!(token_list_data.total_fees_collected, 99);
+    }
+
+    #[test]
+    fn burned_tokens_only_collect_the_fee_received_after_the_transfer_fee() {
+        let (result, accounts) = request_with_transfer_fee(true);
+        assert_eq!(result, Ok(()));
+        // 9_900 is burned and the fee of 100 locked, of which the mint withholds 1
+        assert_eq!(token_amount(&accounts[3]), 0);
+        assert_eq!(token_amount(&accounts[4]), 99);
+        let mint_data =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&accounts[2].data).unwrap();
+        assert_eq!(mint_data.base.supply, 1_000_000 - 9_900);
+        let record_data = accounts[6].unpack::<TransferRequestRecord>();
+        assert_eq!(record_data.amount, 9_999);
+        assert_eq!(record_data.fee, 99);
+        let token_list_data = accounts[1].unpack::<TokenListDictionary>();
+        assert_eq!(token_list_data.total_fees_collected, 99);
+    }
+
+    // ----------------------------- Lamport fees ---------------------------- //
+
+    fn fee_vault_account(program_id: &Pubkey, total_fees_collected: u64) -> TestAccount {
+        let (fee_vault_pda, _) = FeeVault::generate_pda_key(program_id);
+        let fee_vault_data = FeeVault {
+            is_initialized: true,
+            total_fees_collected,
+        };
+        let mut fee_vault = TestAccount::packed(fee_vault_pda, *program_id, &fee_vault_data);
+        fee_vault.lamports = Rent::default().minimum_balance(FeeVault::LEN) + total_fees_collected;
+        fee_vault
+    }
+
+    #[test]
+    fn lamport_fee_is_charged_into_the_fee_vault() {
+        let program_id = Pubkey::new_unique();
+        let (mint, sender) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let bridge = bridge_account(&program_id, &Pubkey::new_unique());
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+        let token_list_data = TokenListDictionary {
+            is_initialized: true,
+            token_address: mint,
+            exists: true,
+            fee: TEN_POW_18 / 100,
+            token_program: spl_token::id(),
+            local_decimals: 6,
+            remote_decimals: 6,
+            lamport_fee: 5_000,
+            ..TokenListDictionary::default()
+        };
+        let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(&program_id, &mint);
+        let bridge_token = token_account(bridge_token_pda, mint, bridge.key, 0);
+        let mut accounts = transfer_request_accounts(
+            &program_id,
+            &sender,
+            bridge,
+            TestAccount::packed(token_list_pda, program_id, &token_list_data),
+            mint_account(mint),
+            token_account(Pubkey::new_unique(), mint, sender, 1_000),
+            bridge_token,
+        );
+        accounts[11] = fee_vault_account(&program_id, 0);
+        let (sender_lamports, vault_lamports) = (accounts[5].lamports, accounts[11].lamports);
+
+        let result = run(&mut accounts, |infos| {
+            transfer_request(
+                &program_id,
+                infos,
+                0,
+                RemoteAddress::Ed25519([1; 32]),
+                1_000,
+                2,
+            )
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(accounts[5].lamports, sender_lamports - 5_000);
+        assert_eq!(accounts[11].lamports, vault_lamports + 5_000);
+        assert_eq!(
+            accounts[11].unpack::<FeeVault>().total_fees_collected,
+            5_000
+        );
+        // the token's own fee is waived, the whole amount is bridged
+        let record_data = accounts[6].unpack::<TransferRequestRecord>();
+        assert_eq!((record_data.amount, record_data.fee), (1_000, 0));
+        let token_list_data = accounts[1].unpack::<TokenListDictionary>();
+        assert_eq!(token_list_data.total_fees_collected, 0);
+    }
+
+    #[test]
+    fn lamport_fee_withdrawal_keeps_the_vault_rent_exempt() {
+        let program_id = Pubkey::new_unique();
+        let (treasurer, treasury) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bridge = bridge_account(&program_id, &Pubkey::new_unique());
+        let mut bridge_data = bridge.unpack::<Bridge>();
+        bridge_data.treasury = treasury;
+        bridge_data.pack_into_slice(&mut bridge.data);
+        let mut accounts = [
+            TestAccount::signer(treasurer),
+            bridge,
+            fee_vault_account(&program_id, 5_000),
+            TestAccount::new(treasury, system_program::id(), 0),
+            role_account(&program_id, Role::Treasurer, &treasurer, true),
+        ];
+        let treasury_lamports = accounts[3].lamports;
+        let withdraw = |accounts: &mut [TestAccount], amount| {
+            run(accounts, |infos| {
+                withdraw_lamport_fees(&program_id, infos, amount)
+            })
+        };
+
+        assert_eq!(
+            withdraw(&mut accounts, 5_001),
+            Err(custom(BridgeError::WithdrawAboveCollectedFees))
+        );
+        assert_eq!(withdraw(&mut accounts, 2_000), Ok(()));
+        assert_eq!(withdraw(&mut accounts, 3_000), Ok(()));
+        assert_eq!(
+            withdraw(&mut accounts, 1),
+            Err(custom(BridgeError::WithdrawAboveCollectedFees))
+        );
+        assert_eq!(accounts[2].unpack::<FeeVault>().total_fees_collected, 0);
+        assert_eq!(
+            accounts[2].lamports,
+            Rent::default().minimum_balance(FeeVault::LEN)
+        );
+        assert_eq!(accounts[3].lamports, treasury_lamports + 5_000);
+
+        // only to the Bridge's treasury
+        accounts[2] = fee_vault_account(&program_id, 5_000);
+        accounts[3] = TestAccount::new(Pubkey::new_unique(), system_program::id(), 0);
+        assert_eq!(
+            withdraw(&mut accounts, 5_000),
+            Err(custom(BridgeError::NotTreasuryAccount))
+        );
+        assert_eq!(accounts[2].unpack::<FeeVault>().total_fees_collected, 5_000);
+    }
+
+    // ------------------------- Validator signatures ------------------------ //
+
+    fn validator_set(validators: &[Pubkey], threshold: u8) -> ValidatorSet {
+        let mut validator_set_data = ValidatorSet {
+            is_initialized: true,
+            threshold,
+            count: validators.len() as u8,
+            ..ValidatorSet::default()
+        };
+        validator_set_data.validators[..validators.len()].copy_from_slice(validators);
+        validator_set_data
+    }
+
+    // checks 'message' against one Ed25519 instruction per entry of 'signed', all ahead of
+    // the Bridge instruction
+    fn check_signed(
+        validator_set_data: &ValidatorSet,
+        signed: &[&[(Pubkey, &[u8])]],
+        message: &[u8],
+    ) -> ProgramResult {
+        let mut instructions: Vec<(Pubkey, Vec<u8>)> = signed
+            .iter()
+            .map(|entries| (ed25519_program::id(), ed25519_instruction_data(entries)))
+            .collect();
+        instructions.push((Pubkey::new_unique(), vec![]));
+        let current_index = instructions.len() as u16 - 1;
+        let mut instructions_sysvar = TestAccount::new(sysvar::instructions::id(), sysvar::id(), 0);
+        instructions_sysvar.data = instructions_sysvar_data(&instructions, current_index);
+        check_validator_signatures(validator_set_data, &instructions_sysvar.info(), message)
+    }
+
+    #[test]
+    fn receipts_need_threshold_validator_signatures() {
+        let validators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let validator_set_data = validator_set(&validators, 2);
+        let message: &[u8] = b"receipt";
+        let insufficient = Err(custom(BridgeError::InsufficientValidatorSignatures));
+
+        assert_eq!(
+            check_signed(&validator_set_data, &[], message),
+            insufficient
+        );
+        assert_eq!(
+            check_signed(&validator_set_data, &[&[(validators[0], message)]], message),
+            insufficient
+        );
+        // in one Ed25519 instruction or spread over several
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[0], message), (validators[2], message)]],
+                message
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[1], message)], &[(validators[2], message)]],
+                message
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[
+                    (validators[0], message),
+                    (validators[1], message),
+                    (validators[2], message)
+                ]],
+                message
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn a_validator_signing_twice_counts_once() {
+        let validators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let validator_set_data = validator_set(&validators, 2);
+        let message: &[u8] = b"receipt";
+        let insufficient = Err(custom(BridgeError::InsufficientValidatorSignatures));
+
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[0], message), (validators[0], message)]],
+                message
+            ),
+            insufficient
+        );
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[0], message)], &[(validators[0], message)]],
+                message
+            ),
+            insufficient
+        );
+    }
+
+    #[test]
+    fn only_validators_signing_the_message_count() {
+        let validators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let validator_set_data = validator_set(&validators, 2);
+        let message: &[u8] = b"receipt";
+        let insufficient = Err(custom(BridgeError::InsufficientValidatorSignatures));
+
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[0], message), (Pubkey::new_unique(), message)]],
+                message
+            ),
+            insufficient
+        );
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[
+                    (validators[0], message),
+                    (validators[1], b"another receipt")
+                ]],
+                message
+            ),
+            insufficient
+        );
+        // a key past the set's count is not a member
+        let mut validator_set_data = validator_set(&validators[..2], 2);
+        validator_set_data.validators[2] = validators[2];
+        assert_eq!(
+            check_signed(
+                &validator_set_data,
+                &[&[(validators[0], message), (validators[2], message)]],
+                message
+            ),
+            insufficient
+        );
+    }
+
+    #[test]
+    fn a_zero_threshold_accepts_no_receipt() {
+        let validators: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+        let message: &[u8] = b"receipt";
+        assert_eq!(
+            check_signed(
+                &validator_set(&validators, 0),
+                &[&[(validators[0], message), (validators[1], message)]],
+                message
+            ),
+            Err(custom(BridgeError::InsufficientValidatorSignatures))
+        );
+    }
+
+    // ------------------------------- Refunds ------------------------------- //
+
+    const LOCAL_CHAIN_ID: u64 = 1;
+
+    struct Refund {
+        program_id: Pubkey,
+        validator: Pubkey,
+        sender: Pubkey,
+        // bridge, validator set, record, token list, bridge token, sender token, mint, token program
+        accounts: Vec<TestAccount>,
+    }
+
+    impl Refund {
+        // a lock/unlock TransferRequest 'index' 0 of 1000 tokens, a fee of 10 included, of a
+        // token whose last fee withdrawal was at 'fees_withdrawn_index'
+        fn new(total_fees_collected: u64, fees_withdrawn_index: u64) -> Self {
+            let program_id = Pubkey::new_unique();
+            let validator = Pubkey::new_unique();
+            let sender = Pubkey::new_unique();
+            let mint = Pubkey::new_unique();
+
+            let mut bridge = bridge_account(&program_id, &Pubkey::new_unique());
+            let mut bridge_data = bridge.unpack::<Bridge>();
+            bridge_data.chain_id = LOCAL_CHAIN_ID;
+            bridge_data.pack_into_slice(&mut bridge.data);
+
+            let (validator_set_pda, _) = ValidatorSet::generate_pda_key(&program_id);
+            let (record_pda, _) = TransferRequestRecord::generate_pda_key(&program_id, 0);
+            let record_data = TransferRequestRecord {
+                is_initialized: true,
+                sender,
+                mint,
+                amount: 1_000,
+                fee: 10,
+                ..TransferRequestRecord::default()
+            };
+            let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+            let token_list_data = TokenListDictionary {
+                is_initialized: true,
+                token_address: mint,
+                exists: true,
+                total_fees_collected,
+                token_program: spl_token::id(),
+                fees_withdrawn_index,
+                ..TokenListDictionary::default()
+            };
+            let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(&program_id, &mint);
+            let bridge_token = token_account(bridge_token_pda, mint, bridge.key, 1_000 + 25);
+
+            Refund {
+                program_id,
+                validator,
+                sender,
+                accounts: vec![
+                    bridge,
+                    TestAccount::packed(
+                        validator_set_pda,
+                        program_id,
+                        &validator_set(&[validator], 1),
+                    ),
+                    TestAccount::packed(record_pda, program_id, &record_data),
+                    TestAccount::packed(token_list_pda, program_id, &token_list_data),
+                    bridge_token,
+                    token_account(Pubkey::new_unique(), mint, sender, 0),
+                    mint_account(mint),
+                    TestAccount::new(spl_token::id(), Pubkey::default(), 0),
+                ],
+            }
+        }
+
+        // runs RefundRequest with the validator's signature over 'signed'
+        fn refund(&mut self, refund_fee: bool, signed: &[u8]) -> ProgramResult {
+            let instructions = [
+                (
+                    ed25519_program::id(),
+                    ed25519_instruction_data(&[(self.validator, signed)]),
+                ),
+                (self.program_id, vec![]),
+            ];
+            let mut instructions_sysvar =
+                TestAccount::new(sysvar::instructions::id(), sysvar::id(), 0);
+            instructions_sysvar.data = instructions_sysvar_data(&instructions, 1);
+
+            let mut infos: Vec<AccountInfo> =
+                self.accounts.iter_mut().map(TestAccount::info).collect();
+            infos.insert(4, instructions_sysvar.info());
+            refund_request(&self.program_id, &infos, 0, refund_fee)
+        }
+
+        fn message(&self, refund_fee: bool) -> Vec<u8> {
+            refund_request_message(&self.program_id, LOCAL_CHAIN_ID, 0, refund_fee)
+        }
+
+        fn record(&self) -> TransferRequestRecord {
+            self.accounts[2].unpack()
+        }
+
+        fn total_fees_collected(&self) -> u64 {
+            self.accounts[3]
+                .unpack::<TokenListDictionary>()
+                .total_fees_collected
+        }
+
+        fn refunded(&self) -> u64 {
+            self.accounts[5]
+                .unpack::<spl_token::state::Account>()
+                .amount
+        }
+    }
+
+    #[test]
+    fn refund_returns_a_fee_collected_since_the_last_withdrawal() {
+        let mut refund = Refund::new(25, 0);
+        let message = refund.message(true);
+        assert_eq!(refund.refund(true, &message), Ok(()));
+        assert!(refund.record().refunded);
+        assert_eq!(refund.record().sender, refund.sender);
+        assert_eq!(refund.refunded(), 1_000);
+        assert_eq!(refund.total_fees_collected(), 15);
+        assert_eq!(
+            refund.refund(true, &message),
+            Err(custom(BridgeError::AlreadyRefunded))
+        );
+
+        // fees since the last withdrawal are all still collected, a refund never takes more
+        let mut refund = Refund::new(4, 0);
+        let message = refund.message(true);
+        assert_eq!(
+            refund.refund(true, &message),
+            Err(custom(BridgeError::Underflow))
+        );
+        assert!(!refund.record().refunded);
+    }
+
+    #[test]
+    fn refund_keeps_a_withdrawn_fee() {
+        // a withdrawal after the request took its fee to the treasury, whatever fees of later
+        // requests are collected now, and however much of them was withdrawn
+        for total_fees_collected in [0, 4, 25] {
+            let mut refund = Refund::new(total_fees_collected, 1);
+            let message = refund.message(true);
+            assert_eq!(refund.refund(true, &message), Ok(()));
+            assert!(refund.record().refunded);
+            assert_eq!(refund.refunded(), 990);
+            assert_eq!(refund.total_fees_collected(), total_fees_collected);
+        }
+    }
+
+    #[test]
+    fn fee_withdrawal_ends_refunds_of_earlier_fees() {
+        let program_id = Pubkey::new_unique();
+        let (treasurer, treasury, mint) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut bridge = bridge_account(&program_id, &Pubkey::new_unique());
+        let mut bridge_data = bridge.unpack::<Bridge>();
+        bridge_data.treasury = treasury;
+        bridge_data.current_index = 7;
+        bridge_data.pack_into_slice(&mut bridge.data);
+
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+        let token_list_data = TokenListDictionary {
+            is_initialized: true,
+            token_address: mint,
+            exists: true,
+            total_fees_collected: 25,
+            token_program: spl_token::id(),
+            fees_withdrawn_index: 3,
+            ..TokenListDictionary::default()
+        };
+        let (bridge_token_pda, _, _, _) = generate_bridge_token_pda(&program_id, &mint);
+        let bridge_token = token_account(bridge_token_pda, mint, bridge.key, 25);
+        let mut accounts = [
+            TestAccount::signer(treasurer),
+            token_account(Pubkey::new_unique(), mint, treasury, 0),
+            bridge,
+            bridge_token,
+            mint_account(mint),
+            TestAccount::packed(token_list_pda, program_id, &token_list_data),
+            role_account(&program_id, Role::Treasurer, &treasurer, true),
+        ];
+
+        // even a partial withdrawal, the Bridge can't tell which requests' fees it took
+        let result = run(&mut accounts, |infos| {
+            withdraw_fees(&program_id, infos, 0, 10)
+        });
+        assert_eq!(result, Ok(()));
+        let token_list_data = accounts[5].unpack::<TokenListDictionary>();
+        assert_eq!(token_list_data.total_fees_collected, 15);
+        assert_eq!(token_list_data.fees_withdrawn_index, 7);
+        let treasury_token_data = accounts[1].unpack::<spl_token::state::Account>();
+        assert_eq!(treasury_token_data.amount, 10);
+    }
+
+    #[test]
+    fn refund_without_the_fee_leaves_collected_fees() {
+        let mut refund = Refund::new(25, 0);
+        let message = refund.message(false);
+        assert_eq!(refund.refund(false, &message), Ok(()));
+        assert!(refund.record().refunded);
+        assert_eq!(refund.refunded(), 990);
+        assert_eq!(refund.total_fees_collected(), 25);
+    }
+
+    #[test]
+    fn refund_needs_a_signature_for_this_deployment() {
+        let mut refund = Refund::new(25, 0);
+        let insufficient = Err(custom(BridgeError::InsufficientValidatorSignatures));
+        let other_chain = refund_request_message(&refund.program_id, LOCAL_CHAIN_ID + 1, 0, true);
+        assert_eq!(refund.refund(true, &other_chain), insufficient);
+        let other_program = refund_request_message(&Pubkey::new_unique(), LOCAL_CHAIN_ID, 0, true);
+        assert_eq!(refund.refund(true, &other_program), insufficient);
+        // signed without the fee
+        let message = refund.message(false);
+        assert_eq!(refund.refund(true, &message), insufficient);
+        assert!(!refund.record().refunded);
+    }
+
+    // -------------------------- Bridge ownership -------------------------- //
+
+    #[test]
+    fn only_owner_loads_the_bridge_pda() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut accounts = [
+            TestAccount::signer(owner),
+            bridge_account(&program_id, &owner),
+        ];
+        let bridge_data = run(&mut accounts, |infos| {
+            only_owner(&program_id, &infos[0], &infos[1]).map(|_| ())
+        });
+        assert_eq!(bridge_data, Ok(()));
+    }
+
+    #[test]
+    fn only_owner_rejects_a_bridge_pda_owned_by_another_program() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut bridge = bridge_account(&program_id, &owner);
+        bridge.owner = Pubkey::new_unique();
+        let mut accounts = [TestAccount::signer(owner), bridge];
+        let result = run(&mut accounts, |infos| {
+            only_owner(&program_id, &infos[0], &infos[1]).map(|_| ())
+        });
+        assert_eq!(result, Err(custom(BridgeError::AccountNotOwnedByBridge)));
+    }
+
+    #[test]
+    fn only_owner_rejects_other_or_unsigned_callers() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut accounts = [
+            TestAccount::signer(Pubkey::new_unique()),
+            bridge_account(&program_id, &owner),
+        ];
+        let result = run(&mut accounts, |infos| {
+            only_owner(&program_id, &infos[0], &infos[1]).map(|_| ())
+        });
+        assert_eq!(result, Err(ProgramError::IllegalOwner));
+
+        let mut accounts = [
+            TestAccount::new(owner, system_program::id(), 0),
+            bridge_account(&program_id, &owner),
+        ];
+        let result = run(&mut accounts, |infos| {
+            only_owner(&program_id, &infos[0], &infos[1]).map(|_| ())
+        });
+        assert_eq!(result, Err(ProgramError::MissingRequiredSignature));
+    }
+
+    // ------------------------------ Guardian -------------------------------- //
+
+    #[test]
+    fn guardian_pauses_but_only_the_owner_unpauses() {
+        let program_id = Pubkey::new_unique();
+        let (owner, guardian) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bridge = guarded_bridge_account(&program_id, &owner, &guardian);
+        let mut call = |caller: Pubkey,
+                        handler: fn(&Pubkey, &[AccountInfo]) -> ProgramResult|
+         -> ProgramResult {
+            let mut caller = TestAccount::signer(caller);
+            handler(&program_id, &[caller.info(), bridge.info()])
+        };
+
+        assert_eq!(
+            call(Pubkey::new_unique(), pause_bridge),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(call(guardian, pause_bridge), Ok(()));
+        assert_eq!(
+            call(owner, pause_bridge),
+            Err(custom(BridgeError::BridgePaused))
+        );
+        assert_eq!(
+            call(guardian, unpause_bridge),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(call(owner, unpause_bridge), Ok(()));
+        assert_eq!(
+            call(owner, unpause_bridge),
+            Err(custom(BridgeError::BridgeNotPaused))
+        );
+        assert_eq!(call(owner, pause_bridge), Ok(()));
+    }
+
+    // -------------------------------- Roles -------------------------------- //
+
+    fn role_account(
+        program_id: &Pubkey,
+        role: Role,
+        member: &Pubkey,
+        has_role: bool,
+    ) -> TestAccount {
+        let (pda, _) = RoleDictionary::generate_pda_key(program_id, role, member);
+        let role_data = RoleDictionary {
+            is_initialized: true,
+            has_role,
+        };
+        TestAccount::packed(pda, *program_id, &role_data)
+    }
+
+    #[test]
+    fn only_role_needs_the_role_granted_at_its_pda() {
+        let program_id = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let check = |mut member: TestAccount, mut role_account: TestAccount| {
+            only_role(
+                &program_id,
+                &member.info(),
+                &role_account.info(),
+                Role::Treasurer,
+            )
+        };
+        let missing_role = Err(custom(BridgeError::MissingRole));
+
+        assert_eq!(
+            check(
+                TestAccount::signer(member),
+                role_account(&program_id, Role::Treasurer, &member, true)
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check(
+                TestAccount::new(member, system_program::id(), 0),
+                role_account(&program_id, Role::Treasurer, &member, true)
+            ),
+            Err(ProgramError::MissingRequiredSignature)
+        );
+        assert_eq!(
+            check(
+                TestAccount::signer(member),
+                role_account(&program_id, Role::Treasurer, &member, false)
+            ),
+            missing_role
+        );
+        assert_eq!(
+            check(
+                TestAccount::signer(member),
+                role_account(&program_id, Role::FeeManager, &member, true)
+            ),
+            missing_role
+        );
+        assert_eq!(
+            check(
+                TestAccount::signer(member),
+                role_account(&program_id, Role::Treasurer, &Pubkey::new_unique(), true)
+            ),
+            missing_role
+        );
+        let mut foreign_role_account = role_account(&program_id, Role::Treasurer, &member, true);
+        foreign_role_account.owner = Pubkey::new_unique();
+        assert_eq!(
+            check(TestAccount::signer(member), foreign_role_account),
+            missing_role
+        );
+    }
+
+    #[test]
+    fn granted_role_is_revoked_in_place() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let member = Pubkey::new_unique();
+        let (role_pda, _) =
+            RoleDictionary::generate_pda_key(&program_id, Role::LimitManager, &member);
+        let mut accounts = [
+            TestAccount::signer(owner),
+            bridge_account(&program_id, &owner),
+            TestAccount::new(role_pda, program_id, RoleDictionary::LEN),
+            TestAccount::rent_sysvar(),
+            TestAccount::new(system_program::id(), Pubkey::default(), 0),
+        ];
+
+        let result = run(&mut accounts, |infos| {
+            grant_role(&program_id, infos, Role::LimitManager, member)
+        });
+        assert_eq!(result, Ok(()));
+        assert!(accounts[2].unpack::<RoleDictionary>().has_role);
+
+        let result = run(&mut accounts, |infos| {
+            revoke_role(&program_id, infos, Role::LimitManager, member)
+        });
+        assert_eq!(result, Ok(()));
+        assert!(!accounts[2].unpack::<RoleDictionary>().has_role);
+    }
+
+    // ---------------------------- Pending claims --------------------------- //
+
+    fn pending_claim_account(program_id: &Pubkey, release_time: u64) -> TestAccount {
+        let (pda, _) = PendingClaim::generate_pda_key(program_id, 1, 0);
+        let pending_claim_data = PendingClaim {
+            is_initialized: true,
+            amount: 100,
+            release_time,
+            ..PendingClaim::default()
+        };
+        TestAccount::packed(pda, *program_id, &pending_claim_data)
+    }
+
+    fn cancel(
+        program_id: &Pubkey,
+        caller: Pubkey,
+        bridge: &mut TestAccount,
+        pending_claim: &mut TestAccount,
+    ) -> ProgramResult {
+        let mut caller = TestAccount::signer(caller);
+        let infos = [caller.info(), bridge.info(), pending_claim.info()];
+        cancel_pending_claim(program_id, &infos, 1, 0)
+    }
+
+    #[test]
+    fn guardian_cancels_a_pending_claim_before_its_release() {
+        let program_id = Pubkey::new_unique();
+        let (owner, guardian) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bridge = guarded_bridge_account(&program_id, &owner, &guardian);
+
+        let mut released = pending_claim_account(&program_id, NOW as u64);
+        assert_eq!(
+            cancel(&program_id, guardian, &mut bridge, &mut released),
+            Err(custom(BridgeError::PendingClaimReleased))
+        );
+
+        let mut pending_claim = pending_claim_account(&program_id, NOW as u64 + 1);
+        assert_eq!(
+            cancel(
+                &program_id,
+                Pubkey::new_unique(),
+                &mut bridge,
+                &mut pending_claim
+            ),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(
+            cancel(&program_id, guardian, &mut bridge, &mut pending_claim),
+            Ok(())
+        );
+        assert!(pending_claim.unpack::<PendingClaim>().cancelled);
+        assert_eq!(
+            cancel(&program_id, owner, &mut bridge, &mut pending_claim),
+            Err(custom(BridgeError::PendingClaimAlreadyDone))
+        );
+    }
+
+    // ---------------------------- Owner multisig --------------------------- //
+
+    struct Multisig {
+        program_id: Pubkey,
+        signers: Vec<Pubkey>,
+        owner_multisig: TestAccount,
+    }
+
+    impl Multisig {
+        fn new(count: usize, threshold: u8) -> Self {
+            let program_id = Pubkey::new_unique();
+            let signers: Vec<Pubkey> = (0..count).map(|_| Pubkey::new_unique()).collect();
+            let mut owner_multisig_data = OwnerMultisig {
+                is_initialized: true,
+                ..OwnerMultisig::default()
+            };
+            set_multisig_signers(&mut owner_multisig_data, &signers, threshold).unwrap();
+            let (pda, _) = OwnerMultisig::generate_pda_key(&program_id);
+            Multisig {
+                program_id,
+                signers,
+                owner_multisig: TestAccount::packed(pda, program_id, &owner_multisig_data),
+            }
+        }
+
+        fn transaction_account(&self, transaction_id: u64) -> TestAccount {
+            let (pda, _) = MultisigTransaction::generate_pda_key(&self.program_id, transaction_id);
+            TestAccount::new(pda, self.program_id, MultisigTransaction::LEN)
+        }
+
+        fn propose(
+            &mut self,
+            proposer: Pubkey,
+            transaction: &mut TestAccount,
+            transaction_accounts: Vec<TransactionAccount>,
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let instruction = BridgeInstruction::PauseBridge.try_to_vec().unwrap();
+            let mut proposer = TestAccount::signer(proposer);
+            let mut rent_sysvar = TestAccount::rent_sysvar();
+            let mut system_program = TestAccount::new(system_program::id(), Pubkey::default(), 0);
+            let infos = [
+                proposer.info(),
+                self.owner_multisig.info(),
+                transaction.info(),
+                rent_sysvar.info(),
+                system_program.info(),
+            ];
+            propose_multisig_transaction(&program_id, &infos, transaction_accounts, instruction)
+        }
+
+        fn approve(
+            &mut self,
+            signer: Pubkey,
+            transaction: &mut TestAccount,
+            transaction_id: u64,
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut signer = TestAccount::signer(signer);
+            let infos = [
+                signer.info(),
+                self.owner_multisig.info(),
+                transaction.info(),
+            ];
+            approve_multisig_transaction(&program_id, &infos, transaction_id)
+        }
+
+        fn execute(
+            &mut self,
+            transaction: &mut TestAccount,
+            transaction_id: u64,
+            transaction_accounts: &mut [TestAccount],
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut bridge_program = TestAccount::new(program_id, Pubkey::default(), 0);
+            let mut infos = vec![
+                self.owner_multisig.info(),
+                transaction.info(),
+                bridge_program.info(),
+            ];
+            infos.extend(transaction_accounts.iter_mut().map(TestAccount::info));
+            execute_multisig_transaction(&program_id, &infos, transaction_id)
+        }
+
+        fn rotate(
+            &mut self,
+            authority: Pubkey,
+            signers: &[Pubkey],
+            threshold: u8,
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut authority = TestAccount::signer(authority);
+            let infos = [authority.info(), self.owner_multisig.info()];
+            update_owner_multisig(&program_id, &infos, signers, threshold)
+        }
+    }
+
+    #[test]
+    fn multisig_signers_must_be_distinct_and_cover_the_threshold() {
+        let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut owner_multisig_data = OwnerMultisig::default();
+        let invalid = Err(custom(BridgeError::InvalidMultisigThreshold));
+
+        assert_eq!(
+            set_multisig_signers(&mut owner_multisig_data, &signers, 0),
+            invalid
+        );
+        assert_eq!(
+            set_multisig_signers(&mut owner_multisig_data, &signers, 4),
+            invalid
+        );
+        assert_eq!(
+            set_multisig_signers(
+                &mut owner_multisig_data,
+                &[signers[0], signers[1], signers[0]],
+                2
+            ),
+            invalid
+        );
+        let too_many: Vec<Pubkey> = (0..=MAX_MULTISIG_SIGNERS)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert_eq!(
+            set_multisig_signers(&mut owner_multisig_data, &too_many, 1),
+            invalid
+        );
+
+        assert_eq!(
+            set_multisig_signers(&mut owner_multisig_data, &signers, 3),
+            Ok(())
+        );
+        assert_eq!(owner_multisig_data.members(), signers.as_slice());
+        assert_eq!(owner_multisig_data.threshold, 3);
+    }
+
+    #[test]
+    fn multisig_transaction_runs_once_the_threshold_approved() {
+        let mut multisig = Multisig::new(3, 2);
+        let [first, second, _] = multisig.signers[..] else {
+            unreachable!()
+        };
+        let (authority, _) = OwnerMultisig::generate_authority_key(&multisig.program_id);
+        let target = Pubkey::new_unique();
+        let transaction_accounts = vec![
+            TransactionAccount {
+                pubkey: authority,
+                is_writable: false,
+            },
+            TransactionAccount {
+                pubkey: target,
+                is_writable: true,
+            },
+        ];
+        let program_id = multisig.program_id;
+        let accounts = |keys: [Pubkey; 2]| keys.map(|key| TestAccount::new(key, program_id, 0));
+
+        let mut transaction = multisig.transaction_account(0);
+        assert_eq!(
+            multisig.propose(
+                Pubkey::new_unique(),
+                &mut transaction,
+                transaction_accounts.clone()
+            ),
+            Err(custom(BridgeError::NotMultisigSigner))
+        );
+        assert_eq!(
+            multisig.propose(first, &mut transaction, transaction_accounts),
+            Ok(())
+        );
+        assert_eq!(
+            multisig
+                .owner_multisig
+                .unpack::<OwnerMultisig>()
+                .transaction_count,
+            1
+        );
+        // the proposal counts as the proposer's approval
+        assert_eq!(transaction.unpack::<MultisigTransaction>().approvals, 0b001);
+        assert_eq!(
+            multisig.approve(first, &mut transaction, 0),
+            Err(custom(BridgeError::AlreadyApproved))
+        );
+        assert_eq!(
+            multisig.execute(&mut transaction, 0, &mut accounts([authority, target])),
+            Err(custom(BridgeError::InsufficientApprovals))
+        );
+
+        assert_eq!(multisig.approve(second, &mut transaction, 0), Ok(()));
+        assert_eq!(transaction.unpack::<MultisigTransaction>().approvals, 0b011);
+
+        // only the approved accounts, in the approved order
+        assert_eq!(
+            multisig.execute(&mut transaction, 0, &mut accounts([target, authority])),
+            Err(custom(BridgeError::TransactionAccountsMismatch))
+        );
+        assert_eq!(
+            multisig.execute(&mut transaction, 0, &mut accounts([authority, target])),
+            Ok(())
+        );
+        assert!(transaction.unpack::<MultisigTransaction>().executed);
+        assert_eq!(
+            multisig.execute(&mut transaction, 0, &mut accounts([authority, target])),
+            Err(custom(BridgeError::TransactionAlreadyExecuted))
+        );
+    }
+
+    #[test]
+    fn rotating_multisig_signers_voids_pending_transactions() {
+        let mut multisig = Multisig::new(2, 2);
+        let [first, second] = multisig.signers[..] else {
+            unreachable!()
+        };
+        let mut transaction = multisig.transaction_account(0);
+        assert_eq!(multisig.propose(first, &mut transaction, vec![]), Ok(()));
+
+        // only the multisig authority, signing through an executed transaction, rotates signers
+        let new_signers = [second, Pubkey::new_unique()];
+        assert_eq!(
+            multisig.rotate(first, &new_signers, 1),
+            Err(ProgramError::IllegalOwner)
+        );
+        let (authority, _) = OwnerMultisig::generate_authority_key(&multisig.program_id);
+        assert_eq!(multisig.rotate(authority, &new_signers, 1), Ok(()));
+
+        let owner_multisig_data = multisig.owner_multisig.unpack::<OwnerMultisig>();
+        assert_eq!(owner_multisig_data.members(), new_signers.as_slice());
+        assert_eq!(owner_multisig_data.signers_seqno, 1);
+
+        assert_eq!(
+            multisig.approve(second, &mut transaction, 0),
+            Err(custom(BridgeError::MultisigSignersChanged))
+        );
+        assert_eq!(
+            multisig.approve(first, &mut transaction, 0),
+            Err(custom(BridgeError::NotMultisigSigner))
+        );
+        assert_eq!(
+            multisig.execute(&mut transaction, 0, &mut []),
+            Err(custom(BridgeError::MultisigSignersChanged))
+        );
+    }
+
+    // ------------------------------ Timelock ------------------------------- //
+
+    struct Timelock {
+        program_id: Pubkey,
+        owner: Pubkey,
+        guardian: Pubkey,
+        bridge: TestAccount,
+    }
+
+    impl Timelock {
+        fn new(timelock_delay: u64) -> Self {
+            let program_id = Pubkey::new_unique();
+            let owner = Pubkey::new_unique();
+            let guardian = Pubkey::new_unique();
+            let mut bridge = guarded_bridge_account(&program_id, &owner, &guardian);
+            let mut bridge_data = bridge.unpack::<Bridge>();
+            bridge_data.timelock_delay = timelock_delay;
+            bridge_data.pack_into_slice(&mut bridge.data);
+            Timelock {
+                program_id,
+                owner,
+                guardian,
+                bridge,
+            }
+        }
+
+        fn action_account(&self, action_id: u64) -> TestAccount {
+            let (pda, _) = AdminAction::generate_pda_key(&self.program_id, action_id);
+            TestAccount::new(pda, self.program_id, AdminAction::LEN)
+        }
+
+        fn queue(
+            &mut self,
+            proposer: Pubkey,
+            admin_action: &mut TestAccount,
+            instruction: BridgeInstruction,
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut proposer = TestAccount::signer(proposer);
+            let mut rent_sysvar = TestAccount::rent_sysvar();
+            let mut system_program = TestAccount::new(system_program::id(), Pubkey::default(), 0);
+            let infos = [
+                proposer.info(),
+                self.bridge.info(),
+                admin_action.info(),
+                rent_sysvar.info(),
+                system_program.info(),
+            ];
+            queue_admin_action(&program_id, &infos, instruction.try_to_vec().unwrap())
+        }
+
+        // executes an action whose instruction only takes the executor and the Bridge
+        fn execute(&mut self, admin_action: &mut TestAccount, action_id: u64) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut executor = TestAccount::signer(Pubkey::new_unique());
+            let infos = [admin_action.info(), executor.info(), self.bridge.info()];
+            execute_admin_action(&program_id, &infos, action_id)
+        }
+
+        fn cancel(
+            &mut self,
+            caller: Pubkey,
+            admin_action: &mut TestAccount,
+            action_id: u64,
+        ) -> ProgramResult {
+            let program_id = self.program_id;
+            let mut caller = TestAccount::signer(caller);
+            let infos = [caller.info(), self.bridge.info(), admin_action.info()];
+            cancel_admin_action(&program_id, &infos, action_id)
+        }
+    }
+
+    #[test]
+    fn admin_action_runs_once_after_the_delay() {
+        let mut timelock = Timelock::new(0);
+        let owner = timelock.owner;
+        let mut admin_action = timelock.action_account(0);
+        let instruction = BridgeInstruction::UpdateTimelockDelayOnlyOwner {
+            timelock_delay: 3_600,
+        };
+
+        assert_eq!(
+            timelock.queue(Pubkey::new_unique(), &mut admin_action, instruction.clone()),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, instruction),
+            Ok(())
+        );
+        let admin_action_data = admin_action.unpack::<AdminAction>();
+        assert_eq!(admin_action_data.proposer, owner);
+        assert_eq!(admin_action_data.eta, NOW as u64);
+        assert_eq!(timelock.bridge.unpack::<Bridge>().action_count, 1);
+
+        assert_eq!(timelock.execute(&mut admin_action, 0), Ok(()));
+        assert!(admin_action.unpack::<AdminAction>().executed);
+        assert_eq!(timelock.bridge.unpack::<Bridge>().timelock_delay, 3_600);
+        assert_eq!(
+            timelock.execute(&mut admin_action, 0),
+            Err(custom(BridgeError::ActionAlreadyDone))
+        );
+
+        // queued under the new delay
+        let mut admin_action = timelock.action_account(1);
+        let instruction = BridgeInstruction::UpdateTimelockDelayOnlyOwner { timelock_delay: 0 };
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, instruction),
+            Ok(())
+        );
+        assert_eq!(admin_action.unpack::<AdminAction>().eta, NOW as u64 + 3_600);
+        assert_eq!(
+            timelock.execute(&mut admin_action, 1),
+            Err(custom(BridgeError::ActionNotReady))
+        );
+    }
+
+    #[test]
+    fn admin_action_only_queues_timelocked_instructions() {
+        let mut timelock = Timelock::new(0);
+        let owner = timelock.owner;
+        let mut admin_action = timelock.action_account(0);
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, BridgeInstruction::PauseBridge),
+            Err(custom(BridgeError::ActionNotTimelocked))
+        );
+        assert_eq!(
+            timelock.queue(
+                owner,
+                &mut timelock.action_account(1),
+                BridgeInstruction::UpdateTimelockDelayOnlyOwner { timelock_delay: 0 }
+            ),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn cancelled_admin_action_never_runs() {
+        let mut timelock = Timelock::new(0);
+        let (owner, guardian) = (timelock.owner, timelock.guardian);
+        let mut admin_action = timelock.action_account(0);
+        let instruction = BridgeInstruction::SetTreasuryOnlyOwner {
+            treasury: Pubkey::new_unique(),
+        };
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, instruction),
+            Ok(())
+        );
+
+        assert_eq!(
+            timelock.cancel(Pubkey::new_unique(), &mut admin_action, 0),
+            Err(ProgramError::IllegalOwner)
+        );
+        assert_eq!(timelock.cancel(guardian, &mut admin_action, 0), Ok(()));
+        assert!(admin_action.unpack::<AdminAction>().cancelled);
+        assert_eq!(
+            timelock.cancel(owner, &mut admin_action, 0),
+            Err(custom(BridgeError::ActionAlreadyDone))
+        );
+        assert_eq!(
+            timelock.execute(&mut admin_action, 0),
+            Err(custom(BridgeError::ActionAlreadyDone))
+        );
+        assert_eq!(
+            timelock.bridge.unpack::<Bridge>().treasury,
+            Pubkey::default()
+        );
+    }
+
+    #[test]
+    fn queue_admin_action_rejects_a_multisig_transaction_as_the_bridge() {
+        let mut timelock = Timelock::new(0);
+        let attacker = Pubkey::new_unique();
+        // a MultisigTransaction proposed by the attacker reads as an initialized Bridge they
+        // own, with no timelock delay
+        let (transaction_pda, _) = MultisigTransaction::generate_pda_key(&timelock.program_id, 0);
+        let multisig_transaction_data = MultisigTransaction {
+            is_initialized: true,
+            proposer: attacker,
+            ..MultisigTransaction::default()
+        };
+        let fake_bridge = TestAccount::packed(
+            transaction_pda,
+            timelock.program_id,
+            &multisig_transaction_data,
+        );
+        let fake_bridge_data = Bridge::unpack_from_slice(&fake_bridge.data).unwrap();
+        assert!(fake_bridge_data.is_initialized);
+        assert_eq!(fake_bridge_data.owner, attacker);
+        assert_eq!(fake_bridge_data.timelock_delay, 0);
+
+        timelock.bridge = fake_bridge;
+        let mut admin_action = timelock.action_account(0);
+        let instruction = BridgeInstruction::GrantRoleOnlyOwner {
+            role: Role::Treasurer,
+            account: attacker,
+        };
+        assert_eq!(
+            timelock.queue(attacker, &mut admin_action, instruction),
+            Err(ProgramError::InvalidSeeds)
+        );
+        assert!(!admin_action.unpack::<AdminAction>().is_initialized);
+    }
+
+    #[test]
+    fn chain_fee_only_changes_through_the_timelock() {
+        let mut timelock = Timelock::new(3_600);
+        let owner = timelock.owner;
+        let program_id = timelock.program_id;
+        let mut chain_registry = chain_registry_account(&program_id, 2);
+        let instruction = BridgeInstruction::UpdateChainOnlyOwner {
+            chain_id: 2,
+            stable_fee: 5,
+            address_kinds: RemoteAddress::ED25519,
+        };
+
+        let mut caller = TestAccount::signer(owner);
+        let infos = [caller.info(), timelock.bridge.info(), chain_registry.info()];
+        assert_eq!(
+            Processor::process(&program_id, &infos, &instruction.try_to_vec().unwrap()),
+            Err(custom(BridgeError::ActionMustBeQueued))
+        );
+
+        let mut admin_action = timelock.action_account(0);
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, instruction),
+            Ok(())
+        );
+        assert_eq!(admin_action.unpack::<AdminAction>().eta, NOW as u64 + 3_600);
+        let mut executor = TestAccount::signer(Pubkey::new_unique());
+        let infos = [
+            admin_action.info(),
+            executor.info(),
+            timelock.bridge.info(),
+            chain_registry.info(),
+        ];
+        assert_eq!(
+            execute_admin_action(&program_id, &infos, 0),
+            Err(custom(BridgeError::ActionNotReady))
+        );
+        assert_eq!(chain_registry.unpack::<ChainRegistry>().stable_fee, 0);
+    }
+
+    #[test]
+    fn queued_chain_update_sets_its_fee_and_address_kinds() {
+        let mut timelock = Timelock::new(0);
+        let owner = timelock.owner;
+        let program_id = timelock.program_id;
+        let mut chain_registry = chain_registry_account(&program_id, 2);
+        let mut admin_action = timelock.action_account(0);
+        let instruction = BridgeInstruction::UpdateChainOnlyOwner {
+            chain_id: 2,
+            stable_fee: 5,
+            address_kinds: RemoteAddress::ED25519,
+        };
+        assert_eq!(
+            timelock.queue(owner, &mut admin_action, instruction),
+            Ok(())
+        );
+
+        let mut executor = TestAccount::signer(Pubkey::new_unique());
+        let infos = [
+            admin_action.info(),
+            executor.info(),
+            timelock.bridge.info(),
+            chain_registry.info(),
+        ];
+        assert_eq!(execute_admin_action(&program_id, &infos, 0), Ok(()));
+        let chain_registry_data = chain_registry.unpack::<ChainRegistry>();
+        assert_eq!(chain_registry_data.stable_fee, 5);
+        assert_eq!(chain_registry_data.address_kinds, RemoteAddress::ED25519);
+        assert!(!chain_registry_data.paused);
+    }
+
+    #[test]
+    fn owner_pauses_a_chain_right_away() {
+        let program_id = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut accounts = [
+            TestAccount::signer(owner),
+            bridge_account(&program_id, &owner),
+            chain_registry_account(&program_id, 2),
+        ];
+        let mut set_paused = |paused| {
+            run(&mut accounts, |infos| {
+                set_chain_paused(&program_id, infos, 2, paused)
+            })
+        };
+        assert_eq!(set_paused(false), Err(custom(BridgeError::ChainNotPaused)));
+        assert_eq!(set_paused(true), Ok(()));
+        assert_eq!(
+            set_paused(true),
+            Err(custom(BridgeError::ChainAlreadyPaused))
+        );
+        assert_eq!(set_paused(false), Ok(()));
+
+        accounts[0] = TestAccount::signer(Pubkey::new_unique());
+        let result = run(&mut accounts, |infos| {
+            set_chain_paused(&program_id, infos, 2, true)
+        });
+        assert_eq!(result, Err(ProgramError::IllegalOwner));
+        assert!(!accounts[2].unpack::<ChainRegistry>().paused);
+    }
+
+    // ----------------------------- Fee schedule ---------------------------- //
+
+    fn fee_tiers(tiers: &[(u64, u64)]) -> Vec<FeeTier> {
+        tiers
+            .iter()
+            .map(|&(threshold, rate)| FeeTier { threshold, rate })
+            .collect()
+    }
+
+    #[test]
+    fn fee_schedule_accepts_increasing_tiers_from_zero() {
+        let tiers = fee_tiers(&[(0, 30), (1_000, 20), (10_000, 10)]);
+        assert_eq!(validate_fee_schedule(&tiers, 1, 500), Ok(()));
+        assert_eq!(validate_fee_schedule(&tiers, 500, 500), Ok(()));
+        // no maximum
+        assert_eq!(validate_fee_schedule(&tiers, 500, 0), Ok(()));
+        // no tiers disables the schedule
+        assert_eq!(validate_fee_schedule(&[], 0, 0), Ok(()));
+        assert_eq!(
+            validate_fee_schedule(&fee_tiers(&[(0, TEN_POW_18 - 1)]), 0, 0),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fee_schedule_rejects_invalid_tiers_and_bounds() {
+        let invalid = Err(custom(BridgeError::InvalidFeeSchedule));
+        for tiers in [
+            // first tier above zero
+            fee_tiers(&[(1, 30)]),
+            // thresholds not strictly increasing
+            fee_tiers(&[(0, 30), (1_000, 20), (1_000, 10)]),
+            fee_tiers(&[(0, 30), (1_000, 20), (500, 10)]),
+            // rate of 100% or more
+            fee_tiers(&[(0, TEN_POW_18)]),
+            // more than MAX_FEE_TIERS
+            (0..=MAX_FEE_TIERS as u64)
+                .map(|i| FeeTier {
+                    threshold: i,
+                    rate: 1,
+                })
+                .collect(),
+        ] {
+            assert_eq!(validate_fee_schedule(&tiers, 0, 0), invalid);
+        }
+        assert_eq!(
+            validate_fee_schedule(&fee_tiers(&[(0, 30)]), 501, 500),
+            invalid
+        );
+    }
+
+    // ------------------------------ Fee quotes ----------------------------- //
+
+    const PERCENT: u64 = TEN_POW_18 / 100;
+
+    // quotes 'amount' of token 0 to chain 2, with the Bridge's 'stable_fee', the token's
+    // 'token_fee', the chain's 'chain_fee' and an optional FeeSchedule
+    fn quote(
+        stable_fee: u64,
+        token_fee: u64,
+        chain_fee: u64,
+        fee_schedule_data: Option<FeeSchedule>,
+        amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let program_id = Pubkey::new_unique();
+        let mut bridge = bridge_account(&program_id, &Pubkey::new_unique());
+        let mut bridge_data = bridge.unpack::<Bridge>();
+        bridge_data.stable_fee = stable_fee;
+        bridge_data.pack_into_slice(&mut bridge.data);
+
+        let (token_list_pda, _, _, _) = TokenListDictionary::generate_pda_key(&program_id, 0);
+        let token_list_data = TokenListDictionary {
+            is_initialized: true,
+            exists: true,
+            fee: token_fee,
+            ..TokenListDictionary::default()
+        };
+        let (chain_registry_pda, _) = ChainRegistry::generate_pda_key(&program_id, 2);
+        let chain_registry_data = ChainRegistry {
+            is_initialized: true,
+            chain_id: 2,
+            enabled: true,
+            stable_fee: chain_fee,
+            ..ChainRegistry::default()
+        };
+        let mut accounts = vec![
+            bridge,
+            TestAccount::packed(token_list_pda, program_id, &token_list_data),
+            TestAccount::packed(chain_registry_pda, program_id, &chain_registry_data),
+        ];
+        if let Some(fee_schedule_data) = fee_schedule_data {
+            let (fee_schedule_pda, _) = FeeSchedule::generate_pda_key(&program_id, 0);
+            accounts.push(TestAccount::packed(
+                fee_schedule_pda,
+                program_id,
+                &fee_schedule_data,
+            ));
+        }
+
+        let mut fee = 0;
+        run(&mut accounts, |infos| {
+            fee = quote_fee(&program_id, infos, 0, amount)?;
+            Ok(())
+        })?;
+        Ok(fee)
+    }
+
+    #[test]
+    fn token_fee_overrides_chain_and_stable_fees() {
+        assert_eq!(quote(PERCENT, 0, 0, None, 10_000), Ok(100));
+        assert_eq!(quote(PERCENT, 0, 2 * PERCENT, None, 10_000), Ok(200));
+        assert_eq!(
+            quote(PERCENT, 3 * PERCENT, 2 * PERCENT, None, 10_000),
+            Ok(300)
+        );
+        // a rate of TEN_POW_18 or more charges nothing
+        assert_eq!(quote(TEN_POW_18, 0, 0, None, 10_000), Ok(0));
+    }
+
+    #[test]
+    fn fee_schedule_overrides_the_token_fee_within_its_bounds() {
+        let fee_schedule_data = FeeSchedule {
+            is_initialized: true,
+            tiers: vec![
+                FeeTier {
+                    threshold: 0,
+                    rate: 2 * PERCENT,
+                },
+                FeeTier {
+                    threshold: 10_000,
+                    rate: PERCENT,
+                },
+            ],
+            min_fee: 50,
+            max_fee: 1_000,
+            ..FeeSchedule::default()
+        };
+        let scheduled = |amount| quote(0, 3 * PERCENT, 0, Some(fee_schedule_data.clone()), amount);
+        assert_eq!(scheduled(5_000), Ok(100));
+        assert_eq!(scheduled(20_000), Ok(200));
+        assert_eq!(scheduled(1_000), Ok(50));
+        assert_eq!(scheduled(1_000_000), Ok(1_000));
+        assert_eq!(scheduled(40), Err(custom(BridgeError::AmountBelowFee)));
+
+        // a schedule without tiers leaves the token fee in place
+        assert_eq!(
+            quote(0, 3 * PERCENT, 0, Some(FeeSchedule::default()), 10_000),
+            Ok(300)
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use crate::error::BridgeError;
+use arrayref::{array_ref, array_refs};
+use solana_program::{
+    account_info::AccountInfo,
+    ed25519_program,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+/// Size of one `Ed25519SignatureOffsets` entry in the Ed25519 program instruction data
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+/// Entries start after the signature count (1 byte) and padding (1 byte)
+const SIGNATURE_OFFSETS_START: usize = 2;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+/// Instruction index used by the Ed25519 program to refer to its own instruction data
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Fields of a TransferReceipt the validators sign
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Receipt {
+    pub token_index: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    /// In the token's 'remote_decimals'
+    pub amount: u64,
+    /// Source chain of the transfer
+    pub chain_id: u64,
+    /// Index of the transfer on its source chain
+    pub index: u64,
+}
+
+/// Builds the canonical message a validator signs to authorize a TransferReceipt.
+///
+/// Layout: program_id (32) | local_chain_id (8) | token_index (8) | from (32) | to (32) |
+/// amount (8) | chain_id (8) | index (8), integers little endian. The program id and the
+/// Bridge's own chain id keep a signature from being replayed on another deployment.
+pub fn transfer_receipt_message(
+    program_id: &Pubkey,
+    local_chain_id: u64,
+    receipt: &Receipt,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 8 + 8 + 32 + 32 + 8 + 8 + 8);
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(&local_chain_id.to_le_bytes());
+    message.extend_from_slice(&receipt.token_index.to_le_bytes());
+    message.extend_from_slice(receipt.from.as_ref());
+    message.extend_from_slice(receipt.to.as_ref());
+    message.extend_from_slice(&receipt.amount.to_le_bytes());
+    message.extend_from_slice(&receipt.chain_id.to_le_bytes());
+    message.extend_from_slice(&receipt.index.to_le_bytes());
+    message
+}
+
+/// Prefix of refund messages, which keeps them apart from receipt messages
+const REFUND_REQUEST_MESSAGE_PREFIX: &[u8] = b"refund_request";
+
+/// Builds the canonical message a validator signs to attest that request 'index' was
+/// rejected on its destination chain and authorize a RefundRequest.
+///
+/// Layout: "refund_request" | program_id (32) | local_chain_id (8) | index (8) |
+/// refund_fee (1), integers little endian. As for receipts, the program id and the Bridge's
+/// own chain id keep a signature from being replayed on another deployment.
+pub fn refund_request_message(
+    program_id: &Pubkey,
+    local_chain_id: u64,
+    index: u64,
+    refund_fee: bool,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(REFUND_REQUEST_MESSAGE_PREFIX.len() + 32 + 8 + 8 + 1);
+    message.extend_from_slice(REFUND_REQUEST_MESSAGE_PREFIX);
+    message.extend_from_slice(program_id.as_ref());
+    message.extend_from_slice(&local_chain_id.to_le_bytes());
+    message.extend_from_slice(&index.to_le_bytes());
+    message.push(refund_fee as u8);
+    message
+}
+
+/// Returns the public keys whose Ed25519 signature over `message` was verified by
+/// an Ed25519 program instruction preceding the current instruction.
+///
+/// The runtime rejects the whole transaction if any Ed25519 instruction fails, so
+/// every entry found here is a valid signature. Only entries whose signature, public
+/// key and message live in the Ed25519 instruction's own data are considered.
+pub fn verified_signers(
+    instructions_sysvar_account: &AccountInfo,
+    message: &[u8],
+) -> Result<Vec<Pubkey>, ProgramError> {
+    let current_index = load_current_index_checked(instructions_sysvar_account)?;
+
+    let mut signers: Vec<Pubkey> = Vec::new();
+    for instruction_index in 0..current_index {
+        let instruction =
+            load_instruction_at_checked(instruction_index as usize, instructions_sysvar_account)?;
+        if instruction.program_id != ed25519_program::id() {
+            continue;
+        }
+
+        let data = instruction.data.as_slice();
+        let num_signatures = match data.first() {
+            Some(num_signatures) => *num_signatures as usize,
+            None => {
+                return Err(ProgramError::Custom(
+                    BridgeError::InvalidSignatureInstruction as u32,
+                ))
+            }
+        };
+
+        for i in 0..num_signatures {
+            let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+            if data.len() < start + SIGNATURE_OFFSETS_SERIALIZED_SIZE {
+                return Err(ProgramError::Custom(
+                    BridgeError::InvalidSignatureInstruction as u32,
+                ));
+            }
+            let offsets = array_ref![data, start, SIGNATURE_OFFSETS_SERIALIZED_SIZE];
+            let (
+                _signature_offset,
+                signature_instruction_index,
+                public_key_offset,
+                public_key_instruction_index,
+                message_data_offset,
+                message_data_size,
+                message_instruction_index,
+            ) = array_refs![offsets, 2, 2, 2, 2, 2, 2, 2];
+
+            if u16::from_le_bytes(*signature_instruction_index) != CURRENT_INSTRUCTION
+                || u16::from_le_bytes(*public_key_instruction_index) != CURRENT_INSTRUCTION
+                || u16::from_le_bytes(*message_instruction_index) != CURRENT_INSTRUCTION
+            {
+                continue;
+            }
+
+            let message_start = u16::from_le_bytes(*message_data_offset) as usize;
+            let message_end = message_start + u16::from_le_bytes(*message_data_size) as usize;
+            let public_key_start = u16::from_le_bytes(*public_key_offset) as usize;
+            let public_key_end = public_key_start + PUBKEY_SERIALIZED_SIZE;
+
+            let signed_message =
+                data.get(message_start..message_end)
+                    .ok_or(ProgramError::Custom(
+                        BridgeError::InvalidSignatureInstruction as u32,
+                    ))?;
+            if signed_message != message {
+                continue;
+            }
+
+            let public_key =
+                data.get(public_key_start..public_key_end)
+                    .ok_or(ProgramError::Custom(
+                        BridgeError::InvalidSignatureInstruction as u32,
+                    ))?;
+            signers.push(Pubkey::new_from_array(*array_ref![
+                public_key,
+                0,
+                PUBKEY_SERIALIZED_SIZE
+            ]));
+        }
+    }
+    Ok(signers)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use solana_program::sysvar::instructions::{
+        construct_instructions_data, store_current_index, BorrowedInstruction,
+    };
+
+    /// Ed25519 program instruction data carrying one signature entry per (key, message),
+    /// everything in the instruction itself. The signatures are zeroed, the runtime
+    /// verifies them before the Bridge runs.
+    pub(crate) fn ed25519_instruction_data(signed: &[(Pubkey, &[u8])]) -> Vec<u8> {
+        let mut data = vec![signed.len() as u8, 0];
+        let mut payload: Vec<u8> = Vec::new();
+        let payload_start =
+            SIGNATURE_OFFSETS_START + signed.len() * SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        for (public_key, message) in signed {
+            let public_key_offset = payload_start + payload.len();
+            payload.extend_from_slice(public_key.as_ref());
+            let signature_offset = payload_start + payload.len();
+            payload.extend_from_slice(&[0; 64]);
+            let message_offset = payload_start + payload.len();
+            payload.extend_from_slice(message);
+            for field in [
+                signature_offset as u16,
+                CURRENT_INSTRUCTION,
+                public_key_offset as u16,
+                CURRENT_INSTRUCTION,
+                message_offset as u16,
+                message.len() as u16,
+                CURRENT_INSTRUCTION,
+            ] {
+                data.extend_from_slice(&field.to_le_bytes());
+            }
+        }
+        data.extend_from_slice(&payload);
+        data
+    }
+
+    /// Instructions sysvar data of a transaction of (program id, data) instructions,
+    /// currently running instruction 'current_index'
+    pub(crate) fn instructions_sysvar_data(
+        instructions: &[(Pubkey, Vec<u8>)],
+        current_index: u16,
+    ) -> Vec<u8> {
+        let borrowed: Vec<BorrowedInstruction> = instructions
+            .iter()
+            .map(|(program_id, data)| BorrowedInstruction {
+                program_id,
+                accounts: vec![],
+                data,
+            })
+            .collect();
+        let mut data = construct_instructions_data(&borrowed);
+        store_current_index(&mut data, current_index);
+        data
+    }
+
+    fn signers_of(
+        instructions: &[(Pubkey, Vec<u8>)],
+        current_index: u16,
+        message: &[u8],
+    ) -> Result<Vec<Pubkey>, ProgramError> {
+        let key = solana_program::sysvar::instructions::id();
+        let owner = solana_program::sysvar::id();
+        let mut lamports = 0;
+        let mut data = instructions_sysvar_data(instructions, current_index);
+        let instructions_sysvar = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        verified_signers(&instructions_sysvar, message)
+    }
+
+    fn invalid_instruction() -> Result<Vec<Pubkey>, ProgramError> {
+        Err(ProgramError::Custom(
+            BridgeError::InvalidSignatureInstruction as u32,
+        ))
+    }
+
+    fn receipt() -> Receipt {
+        Receipt {
+            token_index: 1,
+            from: Pubkey::new_from_array([2; 32]),
+            to: Pubkey::new_from_array([3; 32]),
+            amount: 4,
+            chain_id: 5,
+            index: 6,
+        }
+    }
+
+    #[test]
+    fn receipt_message_layout() {
+        let program_id = Pubkey::new_from_array([9; 32]);
+        let message = transfer_receipt_message(&program_id, 7, &receipt());
+        assert_eq!(message.len(), 136);
+        assert_eq!(&message[0..32], &[9; 32]);
+        assert_eq!(&message[32..40], &7u64.to_le_bytes());
+        assert_eq!(&message[40..48], &1u64.to_le_bytes());
+        assert_eq!(&message[48..80], &[2; 32]);
+        assert_eq!(&message[80..112], &[3; 32]);
+        assert_eq!(&message[112..120], &4u64.to_le_bytes());
+        assert_eq!(&message[120..128], &5u64.to_le_bytes());
+        assert_eq!(&message[128..136], &6u64.to_le_bytes());
+    }
+
+    #[test]
+    fn receipt_message_is_bound_to_the_deployment() {
+        let program_id = Pubkey::new_unique();
+        let message = transfer_receipt_message(&program_id, 7, &receipt());
+        assert_ne!(
+            message,
+            transfer_receipt_message(&Pubkey::new_unique(), 7, &receipt())
+        );
+        assert_ne!(
+            message,
+            transfer_receipt_message(&program_id, 8, &receipt())
+        );
+        let other_index = Receipt {
+            index: 7,
+            ..receipt()
+        };
+        assert_ne!(
+            message,
+            transfer_receipt_message(&program_id, 7, &other_index)
+        );
+    }
+
+    #[test]
+    fn refund_message_layout() {
+        let program_id = Pubkey::new_from_array([9; 32]);
+        let message = refund_request_message(&program_id, 7, 6, true);
+        let prefix_len = REFUND_REQUEST_MESSAGE_PREFIX.len();
+        assert_eq!(message.len(), prefix_len + 49);
+        assert_eq!(&message[..prefix_len], b"refund_request");
+        assert_eq!(&message[prefix_len..prefix_len + 32], &[9; 32]);
+        assert_eq!(
+            &message[prefix_len + 32..prefix_len + 40],
+            &7u64.to_le_bytes()
+        );
+        assert_eq!(
+            &message[prefix_len + 40..prefix_len + 48],
+            &6u64.to_le_bytes()
+        );
+        assert_eq!(message[prefix_len + 48], 1);
+        assert_eq!(
+            refund_request_message(&program_id, 7, 6, false)[prefix_len + 48],
+            0
+        );
+    }
+
+    #[test]
+    fn refund_message_is_bound_to_the_deployment() {
+        let program_id = Pubkey::new_unique();
+        let message = refund_request_message(&program_id, 7, 6, true);
+        assert_ne!(
+            message,
+            refund_request_message(&Pubkey::new_unique(), 7, 6, true)
+        );
+        assert_ne!(message, refund_request_message(&program_id, 8, 6, true));
+        assert_ne!(message, refund_request_message(&program_id, 7, 6, false));
+    }
+
+    #[test]
+    fn signers_of_matching_ed25519_entries_are_returned() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let message: &[u8] = b"receipt";
+        let instructions = [
+            (
+                ed25519_program::id(),
+                ed25519_instruction_data(&[(first, message), (second, b"other")]),
+            ),
+            (Pubkey::new_unique(), vec![1, 2, 3]),
+            (
+                ed25519_program::id(),
+                ed25519_instruction_data(&[(second, message)]),
+            ),
+            (Pubkey::new_unique(), vec![]),
+        ];
+        assert_eq!(
+            signers_of(&instructions, 3, message),
+            Ok(vec![first, second])
+        );
+        assert_eq!(signers_of(&instructions, 3, b"other"), Ok(vec![second]));
+        assert_eq!(signers_of(&instructions, 3, b"unsigned"), Ok(vec![]));
+    }
+
+    #[test]
+    fn signatures_after_the_current_instruction_are_ignored() {
+        let signer = Pubkey::new_unique();
+        let message: &[u8] = b"receipt";
+        let instructions = [
+            (Pubkey::new_unique(), vec![]),
+            (
+                ed25519_program::id(),
+                ed25519_instruction_data(&[(signer, message)]),
+            ),
+        ];
+        assert_eq!(signers_of(&instructions, 0, message), Ok(vec![]));
+    }
+
+    #[test]
+    fn entries_pointing_at_other_instructions_are_ignored() {
+        let signer = Pubkey::new_unique();
+        let message: &[u8] = b"receipt";
+        let signature_fields = [
+            SIGNATURE_OFFSETS_START + 2,
+            SIGNATURE_OFFSETS_START + 6,
+            SIGNATURE_OFFSETS_START + 12,
+        ];
+        for field in signature_fields {
+            let mut data = ed25519_instruction_data(&[(signer, message)]);
+            // signature, public key or message taken from instruction 0
+            data[field..field + 2].copy_from_slice(&0u16.to_le_bytes());
+            let instructions = [
+                (ed25519_program::id(), data),
+                (Pubkey::new_unique(), vec![]),
+            ];
+            assert_eq!(signers_of(&instructions, 1, message), Ok(vec![]));
+        }
+    }
+
+    #[test]
+    fn malformed_ed25519_instructions_are_rejected() {
+        let signer = Pubkey::new_unique();
+        let message: &[u8] = b"receipt";
+        let run = |data: Vec<u8>| {
+            signers_of(
+                &[
+                    (ed25519_program::id(), data),
+                    (Pubkey::new_unique(), vec![]),
+                ],
+                1,
+                message,
+            )
+        };
+
+        assert_eq!(run(vec![]), invalid_instruction());
+        // counts more entries than it holds
+        let mut data = ed25519_instruction_data(&[(signer, message)]);
+        data[0] = 2;
+        data.truncate(SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE + 10);
+        assert_eq!(run(data), invalid_instruction());
+        // message past the end of the data
+        let mut data = ed25519_instruction_data(&[(signer, message)]);
+        let size_field = SIGNATURE_OFFSETS_START + 10;
+        data[size_field..size_field + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(run(data), invalid_instruction());
+        // public key past the end of the data
+        let mut data = ed25519_instruction_data(&[(signer, message)]);
+        let public_key_field = SIGNATURE_OFFSETS_START + 4;
+        let len = data.len() as u16;
+        data[public_key_field..public_key_field + 2].copy_from_slice(&(len - 8).to_le_bytes());
+        let message_field = SIGNATURE_OFFSETS_START + 8;
+        let message_start = len - message.len() as u16;
+        data[message_field..message_field + 2].copy_from_slice(&message_start.to_le_bytes());
+        assert_eq!(run(data), invalid_instruction());
+    }
+}
@@ -1,4 +1,7 @@
+use crate::error::BridgeError;
+use crate::fee::RoundingMode;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -13,22 +16,35 @@ pub trait GeneratePdaKey {
     fn get_constants() -> Vec<String>;
 }
 
+// The layout only supports fresh deployments, a Bridge account created by an earlier version
+// is shorter than LEN and can't be unpacked
 #[derive(Debug, Default, Clone)]
 pub struct Bridge {
-    // total size = (8*6) + (32*2) + 1
+    // total size = (8*6) + 32 + 1
     pub is_initialized: bool,        // 1 byte
     pub owner: Pubkey,               // 32 bytes
     pub fee_update_duration: u64,    //8 bytes
-    pub verify_address: Pubkey,      //32 bytes
     pub current_index: u64,          //8 bytes
     pub chain_id: u64,               //8 bytes
     pub stable_fee_update_time: u64, //8 bytes
     pub stable_fee: u64,             //8 bytes
     pub new_stable_fee: u64,         //8 bytes
+    // blocks transfers, receipts and fee withdrawals for every token
+    pub paused: bool, // 1 byte
+    // can pause the bridge, but only the owner can unpause it
+    pub guardian: Pubkey, // 32 bytes
+    // proposed owner, zero when there is no pending proposal
+    pub pending_owner: Pubkey, // 32 bytes
+    // seconds a queued admin action waits before it can be executed
+    pub timelock_delay: u64, // 8 bytes
+    // number of admin actions queued so far, the id of the next one
+    pub action_count: u64, // 8 bytes
+    // owner of the token accounts fees are withdrawn to, zero until set
+    pub treasury: Pubkey, // 32 bytes
 }
 impl Sealed for Bridge {}
 impl Pack for Bridge {
-    const LEN: usize = 241 - 32 * 4;
+    const LEN: usize = 241 - 32 * 5 + 1 + 32 + 32 + 8 + 8 + 32;
 
     // for deserialization
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
@@ -38,29 +54,44 @@ impl Pack for Bridge {
             is_initialized,
             owner,
             fee_update_duration,
-            verify_address,
             current_index,
             chain_id,
             stable_fee_update_time,
             stable_fee,
             new_stable_fee,
-        ) = array_refs![src, 1, 32, 8, 32, 8, 8, 8, 8, 8];
+            paused,
+            guardian,
+            pending_owner,
+            timelock_delay,
+            action_count,
+            treasury,
+        ) = array_refs![src, 1, 32, 8, 8, 8, 8, 8, 8, 1, 32, 32, 8, 8, 32];
         let is_initialized = match is_initialized {
             [0] => false,
             [1] => true,
             _ => return Err(ProgramError::InvalidAccountData),
         };
+        let paused = match paused {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
 
         Ok(Bridge {
             is_initialized,
             owner: Pubkey::new_from_array(*owner),
             fee_update_duration: u64::from_le_bytes(*fee_update_duration),
-            verify_address: Pubkey::new_from_array(*verify_address),
             current_index: u64::from_le_bytes(*current_index),
             chain_id: u64::from_le_bytes(*chain_id),
             stable_fee_update_time: u64::from_le_bytes(*stable_fee_update_time),
             stable_fee: u64::from_le_bytes(*stable_fee),
             new_stable_fee: u64::from_le_bytes(*new_stable_fee),
+            paused,
+            guardian: Pubkey::new_from_array(*guardian),
+            pending_owner: Pubkey::new_from_array(*pending_owner),
+            timelock_delay: u64::from_le_bytes(*timelock_delay),
+            action_count: u64::from_le_bytes(*action_count),
+            treasury: Pubkey::new_from_array(*treasury),
         })
     }
 
@@ -72,35 +103,50 @@ impl Pack for Bridge {
             is_initialized_dst,
             owner_dst,
             fee_update_duration_dst,
-            verify_address_dst,
             current_index_dst,
             chain_id_dst,
             stable_fee_update_time_dst,
             stable_fee_dst,
             new_stable_fee_dst,
-        ) = mut_array_refs![dst, 1, 32, 8, 32, 8, 8, 8, 8, 8];
+            paused_dst,
+            guardian_dst,
+            pending_owner_dst,
+            timelock_delay_dst,
+            action_count_dst,
+            treasury_dst,
+        ) = mut_array_refs![dst, 1, 32, 8, 8, 8, 8, 8, 8, 1, 32, 32, 8, 8, 32];
 
         let Bridge {
             is_initialized,
             owner,
             fee_update_duration,
-            verify_address,
             current_index,
             chain_id,
             stable_fee_update_time,
             stable_fee,
             new_stable_fee,
+            paused,
+            guardian,
+            pending_owner,
+            timelock_delay,
+            action_count,
+            treasury,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
         owner_dst.copy_from_slice(owner.as_ref());
         *fee_update_duration_dst = fee_update_duration.to_le_bytes();
-        verify_address_dst.copy_from_slice(verify_address.as_ref());
         *current_index_dst = current_index.to_le_bytes();
         *chain_id_dst = chain_id.to_le_bytes();
         *stable_fee_update_time_dst = stable_fee_update_time.to_le_bytes();
         *stable_fee_dst = stable_fee.to_le_bytes();
         *new_stable_fee_dst = new_stable_fee.to_le_bytes();
+        paused_dst[0] = *paused as u8;
+        guardian_dst.copy_from_slice(guardian.as_ref());
+        pending_owner_dst.copy_from_slice(pending_owner.as_ref());
+        *timelock_delay_dst = timelock_delay.to_le_bytes();
+        *action_count_dst = action_count.to_le_bytes();
+        treasury_dst.copy_from_slice(treasury.as_ref());
     }
 }
 impl IsInitialized for Bridge {
@@ -119,6 +165,375 @@ impl Bridge {
     }
 }
 
+/// Maximum number of validators in the ValidatorSet
+pub const MAX_VALIDATORS: usize = 16;
+
+#[derive(Default, Debug, Clone)]
+pub struct ValidatorSet {
+    pub is_initialized: bool, // 1B
+    // number of distinct validator signatures a TransferReceipt needs
+    pub threshold: u8, // 1B
+    // number of used entries in validators
+    pub count: u8,                            // 1B
+    pub validators: [Pubkey; MAX_VALIDATORS], // 32B * MAX_VALIDATORS
+}
+
+impl ValidatorSet {
+    /// Seed for the ValidatorSet PDA, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "validator_set_key";
+    pub fn generate_pda_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                COMMON_BASE_SEED.as_bytes(),
+                ValidatorSet::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.validators[..self.count as usize]
+    }
+
+    pub fn is_member(&self, validator: &Pubkey) -> bool {
+        self.members().contains(validator)
+    }
+}
+
+impl Sealed for ValidatorSet {}
+impl Pack for ValidatorSet {
+    const LEN: usize = 1 + 1 + 1 + 32 * MAX_VALIDATORS;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ValidatorSet::LEN];
+        let (is_initialized_src, threshold_src, count_src, validators_src) =
+            array_refs![src, 1, 1, 1, 32 * MAX_VALIDATORS];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let count = count_src[0];
+        if count as usize > MAX_VALIDATORS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut validators = [Pubkey::default(); MAX_VALIDATORS];
+        for (validator, validator_src) in validators.iter_mut().zip(validators_src.chunks(32)) {
+            *validator = Pubkey::new_from_array(*array_ref![validator_src, 0, 32]);
+        }
+        Ok(Self {
+            is_initialized,
+            threshold: threshold_src[0],
+            count,
+            validators,
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ValidatorSet::LEN];
+        let (is_initialized_dst, threshold_dst, count_dst, validators_dst) =
+            mut_array_refs![dst, 1, 1, 1, 32 * MAX_VALIDATORS];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        threshold_dst[0] = self.threshold;
+        count_dst[0] = self.count;
+        for (validator_dst, validator) in validators_dst.chunks_mut(32).zip(self.validators.iter())
+        {
+            validator_dst.copy_from_slice(validator.as_ref());
+        }
+    }
+}
+impl IsInitialized for ValidatorSet {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Maximum number of signers of the owner multisig
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Multisig that can own the Bridge. Its authority PDA is set as Bridge's owner and signs the
+/// transactions approved by 'threshold' signers.
+#[derive(Default, Debug, Clone)]
+pub struct OwnerMultisig {
+    pub is_initialized: bool, // 1B
+    // number of signer approvals a transaction needs
+    pub threshold: u8, // 1B
+    // number of used entries in signers
+    pub count: u8,                               // 1B
+    pub signers: [Pubkey; MAX_MULTISIG_SIGNERS], // 32B * MAX_MULTISIG_SIGNERS
+    // bumped on every signer change, voids approvals given to older signer sets
+    pub signers_seqno: u64, // 8B
+    // number of transactions proposed so far, the id of the next one
+    pub transaction_count: u64, // 8B
+}
+
+impl OwnerMultisig {
+    /// Seed for the OwnerMultisig PDA, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "owner_multisig_key";
+    /// Seed for the data-less authority PDA signing for the multisig, alongwith COMMON_BASE_SEED
+    pub const AUTHORITY_SEED: &'static str = "owner_multisig_authority_key";
+    pub fn generate_pda_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                COMMON_BASE_SEED.as_bytes(),
+                OwnerMultisig::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+    pub fn generate_authority_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                COMMON_BASE_SEED.as_bytes(),
+                OwnerMultisig::AUTHORITY_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    pub fn members(&self) -> &[Pubkey] {
+        &self.signers[..self.count as usize]
+    }
+}
+
+impl Sealed for OwnerMultisig {}
+impl Pack for OwnerMultisig {
+    const LEN: usize = 1 + 1 + 1 + 32 * MAX_MULTISIG_SIGNERS + 8 + 8;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, OwnerMultisig::LEN];
+        let (
+            is_initialized_src,
+            threshold_src,
+            count_src,
+            signers_src,
+            signers_seqno_src,
+            transaction_count_src,
+        ) = array_refs![src, 1, 1, 1, 32 * MAX_MULTISIG_SIGNERS, 8, 8];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let count = count_src[0];
+        if count as usize > MAX_MULTISIG_SIGNERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut signers = [Pubkey::default(); MAX_MULTISIG_SIGNERS];
+        for (signer, signer_src) in signers.iter_mut().zip(signers_src.chunks(32)) {
+            *signer = Pubkey::new_from_array(*array_ref![signer_src, 0, 32]);
+        }
+        Ok(Self {
+            is_initialized,
+            threshold: threshold_src[0],
+            count,
+            signers,
+            signers_seqno: u64::from_le_bytes(*signers_seqno_src),
+            transaction_count: u64::from_le_bytes(*transaction_count_src),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, OwnerMultisig::LEN];
+        let (
+            is_initialized_dst,
+            threshold_dst,
+            count_dst,
+            signers_dst,
+            signers_seqno_dst,
+            transaction_count_dst,
+        ) = mut_array_refs![dst, 1, 1, 1, 32 * MAX_MULTISIG_SIGNERS, 8, 8];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        threshold_dst[0] = self.threshold;
+        count_dst[0] = self.count;
+        for (signer_dst, signer) in signers_dst.chunks_mut(32).zip(self.signers.iter()) {
+            signer_dst.copy_from_slice(signer.as_ref());
+        }
+        *signers_seqno_dst = self.signers_seqno.to_le_bytes();
+        *transaction_count_dst = self.transaction_count.to_le_bytes();
+    }
+}
+impl IsInitialized for OwnerMultisig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Maximum number of accounts a multisig transaction passes to its instruction
+pub const MAX_MULTISIG_TRANSACTION_ACCOUNTS: usize = 12;
+/// Maximum size of the Borsh encoded BridgeInstruction of a multisig transaction
+pub const MAX_MULTISIG_TRANSACTION_LEN: usize = 256;
+
+/// An account passed to the instruction of a multisig transaction
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TransactionAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
+
+/// A Bridge instruction proposed to the owner multisig, signed by its authority once approved
+#[derive(Default, Debug, Clone)]
+pub struct MultisigTransaction {
+    pub is_initialized: bool, // 1B
+    pub proposer: Pubkey,     // 32B
+    // OwnerMultisig's 'signers_seqno' when proposed
+    pub signers_seqno: u64, // 8B
+    // bit i is set once OwnerMultisig's signer i approved
+    pub approvals: u16, // 2B
+    pub executed: bool, // 1B
+    // 1B length + 33B * MAX_MULTISIG_TRANSACTION_ACCOUNTS
+    pub accounts: Vec<TransactionAccount>,
+    // Borsh encoded BridgeInstruction, 2B length + MAX_MULTISIG_TRANSACTION_LEN
+    pub instruction: Vec<u8>,
+}
+
+impl MultisigTransaction {
+    /// Seed for all MultisigTransaction PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "multisig_transaction_key";
+    pub fn generate_pda_key(program_id: &Pubkey, transaction_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                transaction_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                MultisigTransaction::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+impl Sealed for MultisigTransaction {}
+impl Pack for MultisigTransaction {
+    const LEN: usize = 1
+        + 32
+        + 8
+        + 2
+        + 1
+        + 1
+        + 33 * MAX_MULTISIG_TRANSACTION_ACCOUNTS
+        + 2
+        + MAX_MULTISIG_TRANSACTION_LEN;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, MultisigTransaction::LEN];
+        let (
+            is_initialized_src,
+            proposer_src,
+            signers_seqno_src,
+            approvals_src,
+            executed_src,
+            accounts_len_src,
+            accounts_src,
+            instruction_len_src,
+            instruction_src,
+        ) = array_refs![
+            src,
+            1,
+            32,
+            8,
+            2,
+            1,
+            1,
+            33 * MAX_MULTISIG_TRANSACTION_ACCOUNTS,
+            2,
+            MAX_MULTISIG_TRANSACTION_LEN
+        ];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let executed = match executed_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let accounts_len = accounts_len_src[0] as usize;
+        let instruction_len = u16::from_le_bytes(*instruction_len_src) as usize;
+        if accounts_len > MAX_MULTISIG_TRANSACTION_ACCOUNTS
+            || instruction_len > MAX_MULTISIG_TRANSACTION_LEN
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let mut accounts = Vec::with_capacity(accounts_len);
+        for account_src in accounts_src.chunks(33).take(accounts_len) {
+            let (pubkey_src, is_writable_src) = array_refs![array_ref![account_src, 0, 33], 32, 1];
+            let is_writable = match is_writable_src {
+                [0] => false,
+                [1] => true,
+                _ => return Err(ProgramError::InvalidAccountData),
+            };
+            accounts.push(TransactionAccount {
+                pubkey: Pubkey::new_from_array(*pubkey_src),
+                is_writable,
+            });
+        }
+        Ok(Self {
+            is_initialized,
+            proposer: Pubkey::new_from_array(*proposer_src),
+            signers_seqno: u64::from_le_bytes(*signers_seqno_src),
+            approvals: u16::from_le_bytes(*approvals_src),
+            executed,
+            accounts,
+            instruction: instruction_src[..instruction_len].to_vec(),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, MultisigTransaction::LEN];
+        let (
+            is_initialized_dst,
+            proposer_dst,
+            signers_seqno_dst,
+            approvals_dst,
+            executed_dst,
+            accounts_len_dst,
+            accounts_dst,
+            instruction_len_dst,
+            instruction_dst,
+        ) = mut_array_refs![
+            dst,
+            1,
+            32,
+            8,
+            2,
+            1,
+            1,
+            33 * MAX_MULTISIG_TRANSACTION_ACCOUNTS,
+            2,
+            MAX_MULTISIG_TRANSACTION_LEN
+        ];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        proposer_dst.copy_from_slice(self.proposer.as_ref());
+        *signers_seqno_dst = self.signers_seqno.to_le_bytes();
+        *approvals_dst = self.approvals.to_le_bytes();
+        executed_dst[0] = self.executed as u8;
+        accounts_len_dst[0] = self.accounts.len() as u8;
+        accounts_dst.fill(0);
+        for (account_dst, account) in accounts_dst.chunks_mut(33).zip(self.accounts.iter()) {
+            account_dst[..32].copy_from_slice(account.pubkey.as_ref());
+            account_dst[32] = account.is_writable as u8;
+        }
+        *instruction_len_dst = (self.instruction.len() as u16).to_le_bytes();
+        instruction_dst.fill(0);
+        instruction_dst[..self.instruction.len()].copy_from_slice(&self.instruction);
+    }
+}
+impl IsInitialized for MultisigTransaction {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct ClaimedDictionary {
     pub claimed: bool,
@@ -176,10 +591,32 @@ pub struct TokenListDictionary {
     pub fee_update_time: u64, //8B
     // new fee
     pub new_fee: u64, //8B
-    // daily limit
+    // most that can be claimed within 'limit_window', zero for no limit
     pub limit: u64, //8B
-    // daily limit time
-    pub limit_timestamp: u64, //8B
+    // seconds covered by the rolling claim limit, a multiple of RATE_LIMIT_BUCKETS
+    pub limit_window: u64, //8B
+    // burn on TransferRequest and mint on TransferReceipt instead of lock/unlock
+    pub mint_burn: bool, //1B
+    // most one recipient can claim within 'limit_window', zero for no limit
+    pub recipient_limit: u64, //8B
+    // receipts of at least this amount wait in a PendingClaim, zero to pay all out at once
+    pub settlement_threshold: u64, //8B
+    // seconds a PendingClaim waits before it can be released
+    pub settlement_delay: u64, //8B
+    // SPL Token or Token-2022 program owning the mint
+    pub token_program: Pubkey, //32B
+    // decimals of the mint
+    pub local_decimals: u8, //1B
+    // decimals of the token on the other chains, amounts are scaled between the two
+    pub remote_decimals: u8, //1B
+    // lamports paid into the FeeVault on TransferRequest instead of a fee out of the
+    // bridged amount, zero to take the fee out of the bridged amount
+    pub lamport_fee: u64, //8B
+    // rounding of fees that fall between two token units
+    pub fee_rounding: RoundingMode, //1B
+    // Bridge 'current_index' at the last fee withdrawal, fees of requests before it left
+    // with the treasury and are no longer refunded
+    pub fees_withdrawn_index: u64, //8B
 }
 
 impl TokenListDictionary {
@@ -191,11 +628,47 @@ impl TokenListDictionary {
         let (pda, bump) = Pubkey::find_program_address(seeds, program_id);
         (pda, bump, seed1.to_string(), seed2.to_string())
     }
+
+    /// Splits a local 'amount' into the amount delivered in remote decimals and the local
+    /// dust that the remote decimals can't represent
+    pub fn to_remote_amount(&self, amount: u64) -> Result<(u64, u64), ProgramError> {
+        if self.local_decimals >= self.remote_decimals {
+            let factor = decimals_factor(self.local_decimals - self.remote_decimals)?;
+            Ok((amount / factor, amount % factor))
+        } else {
+            let factor = decimals_factor(self.remote_decimals - self.local_decimals)?;
+            let remote_amount = amount
+                .checked_mul(factor)
+                .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+            Ok((remote_amount, 0))
+        }
+    }
+
+    /// Local amount of a remote 'amount', rounded down so no more than was sent is released
+    pub fn to_local_amount(&self, amount: u64) -> Result<u64, ProgramError> {
+        if self.remote_decimals >= self.local_decimals {
+            let factor = decimals_factor(self.remote_decimals - self.local_decimals)?;
+            Ok(amount / factor)
+        } else {
+            let factor = decimals_factor(self.local_decimals - self.remote_decimals)?;
+            amount
+                .checked_mul(factor)
+                .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))
+        }
+    }
+}
+
+/// 10 to the power of a difference in decimals, failing when it doesn't fit in a u64
+pub fn decimals_factor(decimals: u8) -> Result<u64, ProgramError> {
+    10u64
+        .checked_pow(decimals as u32)
+        .ok_or(ProgramError::Custom(BridgeError::InvalidDecimals as u32))
 }
 
 impl Sealed for TokenListDictionary {}
 impl Pack for TokenListDictionary {
-    const LEN: usize = 1 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8;
+    const LEN: usize =
+        1 + 32 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 32 + 1 + 1 + 8 + 1 + 8;
 
     // for deserialization
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
@@ -210,8 +683,18 @@ impl Pack for TokenListDictionary {
             fee_update_time_src,
             new_fee_src,
             limit_src,
-            limit_timestamp_src,
-        ) = array_refs![src, 1, 32, 1, 1, 8, 8, 8, 8, 8, 8];
+            limit_window_src,
+            mint_burn_src,
+            recipient_limit_src,
+            settlement_threshold_src,
+            settlement_delay_src,
+            token_program_src,
+            local_decimals_src,
+            remote_decimals_src,
+            lamport_fee_src,
+            fee_rounding_src,
+            fees_withdrawn_index_src,
+        ) = array_refs![src, 1, 32, 1, 1, 8, 8, 8, 8, 8, 8, 1, 8, 8, 8, 32, 1, 1, 8, 1, 8];
         let is_initialized = match is_initialized_src {
             [0] => false,
             [1] => true,
@@ -233,7 +716,21 @@ impl Pack for TokenListDictionary {
         let fee_update_time = u64::from_le_bytes(*fee_update_time_src);
         let new_fee = u64::from_le_bytes(*new_fee_src);
         let limit = u64::from_le_bytes(*limit_src);
-        let limit_timestamp = u64::from_le_bytes(*limit_timestamp_src);
+        let limit_window = u64::from_le_bytes(*limit_window_src);
+        let mint_burn = match mint_burn_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let recipient_limit = u64::from_le_bytes(*recipient_limit_src);
+        let settlement_threshold = u64::from_le_bytes(*settlement_threshold_src);
+        let settlement_delay = u64::from_le_bytes(*settlement_delay_src);
+        let token_program = Pubkey::new_from_array(*token_program_src);
+        let local_decimals = local_decimals_src[0];
+        let remote_decimals = remote_decimals_src[0];
+        let lamport_fee = u64::from_le_bytes(*lamport_fee_src);
+        let fee_rounding = RoundingMode::try_from(fee_rounding_src[0])?;
+        let fees_withdrawn_index = u64::from_le_bytes(*fees_withdrawn_index_src);
         Ok(Self {
             is_initialized,
             token_address,
@@ -244,7 +741,17 @@ impl Pack for TokenListDictionary {
             fee_update_time,
             new_fee,
             limit,
-            limit_timestamp,
+            limit_window,
+            mint_burn,
+            recipient_limit,
+            settlement_threshold,
+            settlement_delay,
+            token_program,
+            local_decimals,
+            remote_decimals,
+            lamport_fee,
+            fee_rounding,
+            fees_withdrawn_index,
         })
     }
 
@@ -262,8 +769,18 @@ impl Pack for TokenListDictionary {
             fee_update_time_dst,
             new_fee_dst,
             limit_dst,
-            limit_timestamp_dst,
-        ) = mut_array_refs![dst, 1, 32, 1, 1, 8, 8, 8, 8, 8, 8];
+            limit_window_dst,
+            mint_burn_dst,
+            recipient_limit_dst,
+            settlement_threshold_dst,
+            settlement_delay_dst,
+            token_program_dst,
+            local_decimals_dst,
+            remote_decimals_dst,
+            lamport_fee_dst,
+            fee_rounding_dst,
+            fees_withdrawn_index_dst,
+        ) = mut_array_refs![dst, 1, 32, 1, 1, 8, 8, 8, 8, 8, 8, 1, 8, 8, 8, 32, 1, 1, 8, 1, 8];
 
         is_initialized_dst[0] = self.is_initialized as u8;
         token_address_dst.copy_from_slice(self.token_address.as_ref());
@@ -274,30 +791,150 @@ impl Pack for TokenListDictionary {
         *fee_update_time_dst = self.fee_update_time.to_le_bytes();
         *new_fee_dst = self.new_fee.to_le_bytes();
         *limit_dst = self.limit.to_le_bytes();
-        *limit_timestamp_dst = self.limit_timestamp.to_le_bytes();
+        *limit_window_dst = self.limit_window.to_le_bytes();
+        mint_burn_dst[0] = self.mint_burn as u8;
+        *recipient_limit_dst = self.recipient_limit.to_le_bytes();
+        *settlement_threshold_dst = self.settlement_threshold.to_le_bytes();
+        *settlement_delay_dst = self.settlement_delay.to_le_bytes();
+        token_program_dst.copy_from_slice(self.token_program.as_ref());
+        local_decimals_dst[0] = self.local_decimals;
+        remote_decimals_dst[0] = self.remote_decimals;
+        *lamport_fee_dst = self.lamport_fee.to_le_bytes();
+        fee_rounding_dst[0] = self.fee_rounding as u8;
+        *fees_withdrawn_index_dst = self.fees_withdrawn_index.to_le_bytes();
+    }
+}
+
+/// Number of buckets a rolling limit window is split into
+pub const RATE_LIMIT_BUCKETS: usize = 24;
+
+/// Amounts moved within a rolling window, kept in RATE_LIMIT_BUCKETS buckets of
+/// window / RATE_LIMIT_BUCKETS seconds each
+#[derive(Default, Debug, Clone)]
+pub struct RateLimitWindow {
+    // bucket holding the most recent amounts, counted in bucket durations since the epoch
+    pub last_bucket: u64, // 8B
+    // amounts per bucket, indexed by bucket % RATE_LIMIT_BUCKETS
+    pub buckets: [u64; RATE_LIMIT_BUCKETS], // 8B * 24
+}
+impl Sealed for RateLimitWindow {}
+impl Pack for RateLimitWindow {
+    const LEN: usize = 8 + 8 * RATE_LIMIT_BUCKETS;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RateLimitWindow::LEN];
+        let (last_bucket, buckets_src) = array_refs![src, 8, 8 * RATE_LIMIT_BUCKETS];
+        let mut buckets = [0u64; RATE_LIMIT_BUCKETS];
+        for (bucket, bucket_src) in buckets.iter_mut().zip(buckets_src.chunks(8)) {
+            *bucket = u64::from_le_bytes(*array_ref![bucket_src, 0, 8]);
+        }
+        Ok(Self {
+            last_bucket: u64::from_le_bytes(*last_bucket),
+            buckets,
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RateLimitWindow::LEN];
+        let (last_bucket_dst, buckets_dst) = mut_array_refs![dst, 8, 8 * RATE_LIMIT_BUCKETS];
+        *last_bucket_dst = self.last_bucket.to_le_bytes();
+        for (bucket, bucket_dst) in self.buckets.iter().zip(buckets_dst.chunks_mut(8)) {
+            bucket_dst.copy_from_slice(&bucket.to_le_bytes());
+        }
+    }
+}
+impl RateLimitWindow {
+    /// Bucket 'timestamp' falls in, for a 'window' seconds long window
+    pub fn bucket(timestamp: u64, window: u64) -> u64 {
+        let bucket_duration = (window / RATE_LIMIT_BUCKETS as u64).max(1);
+        timestamp / bucket_duration
+    }
+
+    /// Moves the window forward to end at 'timestamp', clearing the buckets that fell out of it
+    pub fn roll(&mut self, timestamp: u64, window: u64) {
+        let bucket = RateLimitWindow::bucket(timestamp, window);
+        if bucket <= self.last_bucket {
+            return;
+        }
+        if bucket - self.last_bucket >= RATE_LIMIT_BUCKETS as u64 {
+            self.buckets = [0; RATE_LIMIT_BUCKETS];
+        } else {
+            for expired in self.last_bucket + 1..=bucket {
+                self.buckets[expired as usize % RATE_LIMIT_BUCKETS] = 0;
+            }
+        }
+        self.last_bucket = bucket;
+    }
+
+    /// Whether 'window' seconds split evenly into RATE_LIMIT_BUCKETS buckets
+    pub fn is_valid_window(window: u64) -> bool {
+        window > 0 && window.checked_rem(RATE_LIMIT_BUCKETS as u64) == Some(0)
+    }
+
+    /// Whether adding 'amount' would take the window total above 'limit'
+    pub fn exceeds(&self, amount: u64, limit: u64) -> bool {
+        match self.window_total().checked_add(amount) {
+            Some(total) => total > limit,
+            None => true,
+        }
+    }
+
+    /// Amount moved within the window ending in 'last_bucket'
+    pub fn window_total(&self) -> u64 {
+        self.buckets
+            .iter()
+            .fold(0u64, |total, amount| total.saturating_add(*amount))
+    }
+
+    /// Adds 'amount' to the bucket of the most recent amounts
+    pub fn record(&mut self, amount: u64) -> Result<(), ProgramError> {
+        let bucket = &mut self.buckets[self.last_bucket as usize % RATE_LIMIT_BUCKETS];
+        *bucket = bucket
+            .checked_add(amount)
+            .ok_or(ProgramError::Custom(BridgeError::Overflow as u32))?;
+        Ok(())
+    }
+
+    /// Switches from 'old_window' to 'new_window' at 'timestamp'. Buckets change length
+    /// with the window, so the amounts still inside the old window are carried into the
+    /// current bucket of the new one.
+    pub fn set_window(
+        &mut self,
+        timestamp: u64,
+        old_window: u64,
+        new_window: u64,
+    ) -> Result<(), ProgramError> {
+        self.roll(timestamp, old_window);
+        let carried = self.window_total();
+        *self = RateLimitWindow {
+            last_bucket: RateLimitWindow::bucket(timestamp, new_window),
+            ..Default::default()
+        };
+        self.record(carried)
     }
 }
 
 #[derive(Default, Debug, Clone)]
 pub struct DailyTokenClaimsDictionary {
-    pub daily_token_claims: u64,
+    // claims within the token's 'limit_window'
+    pub claims: RateLimitWindow, // 200B
 }
 impl Sealed for DailyTokenClaimsDictionary {}
 impl Pack for DailyTokenClaimsDictionary {
-    const LEN: usize = 8;
+    const LEN: usize = RateLimitWindow::LEN;
 
     // for deserialization
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let src = array_ref![src, 0, DailyTokenClaimsDictionary::LEN];
         Ok(Self {
-            daily_token_claims: u64::from_le_bytes(*src),
+            claims: RateLimitWindow::unpack_from_slice(src)?,
         })
     }
 
     // for serialization
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let dst = array_mut_ref![dst, 0, DailyTokenClaimsDictionary::LEN];
-        *dst = self.daily_token_claims.to_le_bytes();
+        self.claims.pack_into_slice(dst);
     }
 }
 impl DailyTokenClaimsDictionary {
@@ -314,72 +951,1315 @@ impl DailyTokenClaimsDictionary {
     }
 }
 
+/// A receipt at or above its token's 'settlement_threshold', waiting to be released
 #[derive(Default, Debug, Clone)]
-pub struct TokenAddedDictionary {
-    pub token_added: bool,
+pub struct PendingClaim {
+    pub is_initialized: bool, // 1B
+    pub token_index: u64,     // 8B
+    pub from: Pubkey,         // 32B
+    pub to: Pubkey,           // 32B
+    pub amount: u64,          // 8B
+    // earliest time ExecutePendingClaim can release it
+    pub release_time: u64, // 8B
+    pub executed: bool,    // 1B
+    pub cancelled: bool,   // 1B
 }
-impl Sealed for TokenAddedDictionary {}
-impl Pack for TokenAddedDictionary {
-    const LEN: usize = 1;
+impl Sealed for PendingClaim {}
+impl Pack for PendingClaim {
+    const LEN: usize = 1 + 8 + 32 + 32 + 8 + 8 + 1 + 1;
 
     // for deserialization
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let src = array_ref![src, 0, TokenAddedDictionary::LEN];
-        let token_added = match src {
+        let src = array_ref![src, 0, PendingClaim::LEN];
+        let (is_initialized, token_index, from, to, amount, release_time, executed, cancelled) =
+            array_refs![src, 1, 8, 32, 32, 8, 8, 1, 1];
+        let unpack_bool = |src: &[u8; 1]| match src {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized: unpack_bool(is_initialized)?,
+            token_index: u64::from_le_bytes(*token_index),
+            from: Pubkey::new_from_array(*from),
+            to: Pubkey::new_from_array(*to),
+            amount: u64::from_le_bytes(*amount),
+            release_time: u64::from_le_bytes(*release_time),
+            executed: unpack_bool(executed)?,
+            cancelled: unpack_bool(cancelled)?,
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, PendingClaim::LEN];
+        let (
+            is_initialized_dst,
+            token_index_dst,
+            from_dst,
+            to_dst,
+            amount_dst,
+            release_time_dst,
+            executed_dst,
+            cancelled_dst,
+        ) = mut_array_refs![dst, 1, 8, 32, 32, 8, 8, 1, 1];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *token_index_dst = self.token_index.to_le_bytes();
+        from_dst.copy_from_slice(self.from.as_ref());
+        to_dst.copy_from_slice(self.to.as_ref());
+        *amount_dst = self.amount.to_le_bytes();
+        *release_time_dst = self.release_time.to_le_bytes();
+        executed_dst[0] = self.executed as u8;
+        cancelled_dst[0] = self.cancelled as u8;
+    }
+}
+impl PendingClaim {
+    /// Seed for all PendingClaim PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "pending_claim_key";
+    pub fn generate_pda_key(program_id: &Pubkey, chain_id: u64, index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                chain_id.to_le_bytes().as_ref(),
+                index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                PendingClaim::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+/// Claims paid out to one recipient of a token, checked against the token's 'recipient_limit'
+#[derive(Default, Debug, Clone)]
+pub struct RecipientClaimsDictionary {
+    pub is_initialized: bool, // 1B
+    // 'limit_window' the claims are bucketed with, zero until the first claim
+    pub window: u64,             // 8B
+    pub claims: RateLimitWindow, // 200B
+}
+impl Sealed for RecipientClaimsDictionary {}
+impl Pack for RecipientClaimsDictionary {
+    const LEN: usize = 1 + 8 + RateLimitWindow::LEN;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RecipientClaimsDictionary::LEN];
+        let (is_initialized, window, claims) = array_refs![src, 1, 8, RateLimitWindow::LEN];
+        let is_initialized = match is_initialized {
             [0] => false,
             [1] => true,
-            _ => panic!(),
+            _ => return Err(ProgramError::InvalidAccountData),
         };
-        Ok(Self { token_added })
+        Ok(Self {
+            is_initialized,
+            window: u64::from_le_bytes(*window),
+            claims: RateLimitWindow::unpack_from_slice(claims)?,
+        })
     }
 
     // for serialization
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let dst = array_mut_ref![dst, 0, TokenAddedDictionary::LEN];
-        dst[0] = self.token_added as u8;
+        let dst = array_mut_ref![dst, 0, RecipientClaimsDictionary::LEN];
+        let (is_initialized_dst, window_dst, claims_dst) =
+            mut_array_refs![dst, 1, 8, RateLimitWindow::LEN];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *window_dst = self.window.to_le_bytes();
+        self.claims.pack_into_slice(claims_dst);
     }
 }
-
-impl TokenAddedDictionary {
+impl RecipientClaimsDictionary {
+    /// Seed for all RecipientClaimsDictionary PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "recipient_claims_key";
     pub fn generate_pda_key(
         program_id: &Pubkey,
-        token_mint_address: &Pubkey,
-    ) -> (Pubkey, u8, String, String) {
-        let seed1 = "bridge";
-        let seed2 = "token_added_dictionary_key";
-        let (pda, bump) = Pubkey::find_program_address(
+        token_index: u64,
+        recipient: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
             &[
-                token_mint_address.as_ref(),
-                seed1.as_bytes(),
-                seed2.as_bytes(),
+                token_index.to_le_bytes().as_ref(),
+                recipient.as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                RecipientClaimsDictionary::BASE_SEED.as_bytes(),
             ],
             program_id,
-        );
-        (pda, bump, seed1.to_string(), seed2.to_string())
+        )
     }
 }
 
+/// Caps what TransferRequest can send out of Solana for one token
 #[derive(Default, Debug, Clone)]
-pub struct CalcuateFeeResult {
-    pub fee: u64,
+pub struct OutboundLimitDictionary {
+    pub is_initialized: bool, // 1B
+    // most that can be requested within 'window', zero for no limit
+    pub limit: u64, // 8B
+    // seconds covered by the limit, a multiple of RATE_LIMIT_BUCKETS
+    pub window: u64, // 8B
+    // requested amounts, fees included
+    pub transfers: RateLimitWindow, // 200B
 }
-impl Sealed for CalcuateFeeResult {}
-impl Pack for CalcuateFeeResult {
-    const LEN: usize = 8;
+impl Sealed for OutboundLimitDictionary {}
+impl Pack for OutboundLimitDictionary {
+    const LEN: usize = 1 + 8 + 8 + RateLimitWindow::LEN;
 
     // for deserialization
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        let src: &[u8; 8] = array_ref![src, 0, CalcuateFeeResult::LEN];
-        let fee: u64 = u64::from_le_bytes(*src);
-        Ok(Self { fee })
+        let src = array_ref![src, 0, OutboundLimitDictionary::LEN];
+        let (is_initialized, limit, window, transfers) =
+            array_refs![src, 1, 8, 8, RateLimitWindow::LEN];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            limit: u64::from_le_bytes(*limit),
+            window: u64::from_le_bytes(*window),
+            transfers: RateLimitWindow::unpack_from_slice(transfers)?,
+        })
     }
 
     // for serialization
     fn pack_into_slice(&self, dst: &mut [u8]) {
-        let fee_dst: &mut [u8; 8] = array_mut_ref![dst, 0, CalcuateFeeResult::LEN];
-
-        let CalcuateFeeResult { fee } = self;
+        let dst = array_mut_ref![dst, 0, OutboundLimitDictionary::LEN];
+        let (is_initialized_dst, limit_dst, window_dst, transfers_dst) =
+            mut_array_refs![dst, 1, 8, 8, RateLimitWindow::LEN];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *limit_dst = self.limit.to_le_bytes();
+        *window_dst = self.window.to_le_bytes();
+        self.transfers.pack_into_slice(transfers_dst);
+    }
+}
+impl OutboundLimitDictionary {
+    /// Seed for all OutboundLimitDictionary PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "outbound_limit_key";
+    pub fn generate_pda_key(program_id: &Pubkey, token_index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                token_index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                OutboundLimitDictionary::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+/// Most bytes a remote token identifier can take
+pub const MAX_REMOTE_TOKEN_LEN: usize = 64;
+
+/// Identifier of a token on another chain, e.g. a Casper contract hash
+#[derive(Default, Debug, Clone)]
+pub struct RemoteTokenMapping {
+    pub is_initialized: bool, // 1B
+    // identifier of the token on the remote chain, at most MAX_REMOTE_TOKEN_LEN bytes
+    pub remote_token: Vec<u8>, // 1B length + MAX_REMOTE_TOKEN_LEN
+}
+impl Sealed for RemoteTokenMapping {}
+impl Pack for RemoteTokenMapping {
+    const LEN: usize = 1 + 1 + MAX_REMOTE_TOKEN_LEN;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RemoteTokenMapping::LEN];
+        let (is_initialized, remote_token_len, remote_token) =
+            array_refs![src, 1, 1, MAX_REMOTE_TOKEN_LEN];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let remote_token_len = remote_token_len[0] as usize;
+        if remote_token_len > MAX_REMOTE_TOKEN_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self {
+            is_initialized,
+            remote_token: remote_token[..remote_token_len].to_vec(),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RemoteTokenMapping::LEN];
+        let (is_initialized_dst, remote_token_len_dst, remote_token_dst) =
+            mut_array_refs![dst, 1, 1, MAX_REMOTE_TOKEN_LEN];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        remote_token_len_dst[0] = self.remote_token.len() as u8;
+        remote_token_dst.fill(0);
+        remote_token_dst[..self.remote_token.len()].copy_from_slice(&self.remote_token);
+    }
+}
+impl RemoteTokenMapping {
+    /// Seed for all RemoteTokenMapping PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "remote_token_key";
+    pub fn generate_pda_key(program_id: &Pubkey, token_index: u64, chain_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                token_index.to_le_bytes().as_ref(),
+                chain_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                RemoteTokenMapping::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+/// Most tiers a FeeSchedule can hold
+pub const MAX_FEE_TIERS: usize = 8;
+
+/// Rate, out of TEN_POW_18, charged on amounts of at least 'threshold'
+#[derive(BorshSerialize, BorshDeserialize, Default, Debug, Clone, Copy, PartialEq)]
+pub struct FeeTier {
+    pub threshold: u64, // 8B
+    pub rate: u64,      // 8B
+}
+
+/// Fees of a token by amount, bounded by 'min_fee' and 'max_fee'. Replaces the token's
+/// proportional fee while it has tiers.
+#[derive(Default, Debug, Clone)]
+pub struct FeeSchedule {
+    pub is_initialized: bool, // 1B
+    // by increasing threshold, the first one at zero. No tiers disables the schedule
+    pub tiers: Vec<FeeTier>, // 1B length + MAX_FEE_TIERS * 16B
+    pub min_fee: u64,        // 8B
+    // zero for no maximum
+    pub max_fee: u64, // 8B
+    // time after which the new schedule is active, zero when none is scheduled
+    pub update_time: u64,        // 8B
+    pub new_tiers: Vec<FeeTier>, // 1B length + MAX_FEE_TIERS * 16B
+    pub new_min_fee: u64,        // 8B
+    pub new_max_fee: u64,        // 8B
+}
+
+impl FeeSchedule {
+    /// Seed for all FeeSchedule PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "fee_schedule_key";
+    pub fn generate_pda_key(program_id: &Pubkey, token_index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                token_index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                FeeSchedule::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Rate of the last tier 'amount' reaches
+    pub fn rate(&self, amount: u64) -> u64 {
+        self.tiers
+            .iter()
+            .take_while(|tier| tier.threshold <= amount)
+            .last()
+            .map(|tier| tier.rate)
+            .unwrap_or(0)
+    }
+
+    fn unpack_tiers(src: &[u8; 1 + MAX_FEE_TIERS * 16]) -> Result<Vec<FeeTier>, ProgramError> {
+        let (len_src, tiers_src) = array_refs![src, 1, MAX_FEE_TIERS * 16];
+        let len = len_src[0] as usize;
+        if len > MAX_FEE_TIERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(tiers_src[..len * 16]
+            .chunks_exact(16)
+            .map(|tier_src| {
+                let tier_src = array_ref![tier_src, 0, 16];
+                let (threshold, rate) = array_refs![tier_src, 8, 8];
+                FeeTier {
+                    threshold: u64::from_le_bytes(*threshold),
+                    rate: u64::from_le_bytes(*rate),
+                }
+            })
+            .collect())
+    }
+
+    fn pack_tiers(tiers: &[FeeTier], dst: &mut [u8; 1 + MAX_FEE_TIERS * 16]) {
+        let (len_dst, tiers_dst) = mut_array_refs![dst, 1, MAX_FEE_TIERS * 16];
+        len_dst[0] = tiers.len() as u8;
+        tiers_dst.fill(0);
+        for (tier, tier_dst) in tiers.iter().zip(tiers_dst.chunks_exact_mut(16)) {
+            tier_dst[..8].copy_from_slice(&tier.threshold.to_le_bytes());
+            tier_dst[8..].copy_from_slice(&tier.rate.to_le_bytes());
+        }
+    }
+}
+
+impl Sealed for FeeSchedule {}
+impl Pack for FeeSchedule {
+    const LEN: usize = 1 + (1 + MAX_FEE_TIERS * 16) + 8 + 8 + 8 + (1 + MAX_FEE_TIERS * 16) + 8 + 8;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, FeeSchedule::LEN];
+        let (
+            is_initialized_src,
+            tiers_src,
+            min_fee_src,
+            max_fee_src,
+            update_time_src,
+            new_tiers_src,
+            new_min_fee_src,
+            new_max_fee_src,
+        ) = array_refs![
+            src,
+            1,
+            1 + MAX_FEE_TIERS * 16,
+            8,
+            8,
+            8,
+            1 + MAX_FEE_TIERS * 16,
+            8,
+            8
+        ];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            tiers: FeeSchedule::unpack_tiers(tiers_src)?,
+            min_fee: u64::from_le_bytes(*min_fee_src),
+            max_fee: u64::from_le_bytes(*max_fee_src),
+            update_time: u64::from_le_bytes(*update_time_src),
+            new_tiers: FeeSchedule::unpack_tiers(new_tiers_src)?,
+            new_min_fee: u64::from_le_bytes(*new_min_fee_src),
+            new_max_fee: u64::from_le_bytes(*new_max_fee_src),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, FeeSchedule::LEN];
+        let (
+            is_initialized_dst,
+            tiers_dst,
+            min_fee_dst,
+            max_fee_dst,
+            update_time_dst,
+            new_tiers_dst,
+            new_min_fee_dst,
+            new_max_fee_dst,
+        ) = mut_array_refs![
+            dst,
+            1,
+            1 + MAX_FEE_TIERS * 16,
+            8,
+            8,
+            8,
+            1 + MAX_FEE_TIERS * 16,
+            8,
+            8
+        ];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        FeeSchedule::pack_tiers(&self.tiers, tiers_dst);
+        *min_fee_dst = self.min_fee.to_le_bytes();
+        *max_fee_dst = self.max_fee.to_le_bytes();
+        *update_time_dst = self.update_time.to_le_bytes();
+        FeeSchedule::pack_tiers(&self.new_tiers, new_tiers_dst);
+        *new_min_fee_dst = self.new_min_fee.to_le_bytes();
+        *new_max_fee_dst = self.new_max_fee.to_le_bytes();
+    }
+}
+impl IsInitialized for FeeSchedule {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Holds the lamport fees paid on TransferRequest for tokens with a 'lamport_fee'
+#[derive(Default, Debug, Clone)]
+pub struct FeeVault {
+    pub is_initialized: bool, // 1B
+    // lamports paid in and not withdrawn yet, the rent exempt reserve excluded
+    pub total_fees_collected: u64, // 8B
+}
+impl Sealed for FeeVault {}
+impl Pack for FeeVault {
+    const LEN: usize = 1 + 8;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, FeeVault::LEN];
+        let (is_initialized, total_fees_collected) = array_refs![src, 1, 8];
+        let is_initialized = match is_initialized {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            total_fees_collected: u64::from_le_bytes(*total_fees_collected),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, FeeVault::LEN];
+        let (is_initialized_dst, total_fees_collected_dst) = mut_array_refs![dst, 1, 8];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *total_fees_collected_dst = self.total_fees_collected.to_le_bytes();
+    }
+}
+impl FeeVault {
+    /// Seed for the FeeVault PDA, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "fee_vault_key";
+    pub fn generate_pda_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[COMMON_BASE_SEED.as_bytes(), FeeVault::BASE_SEED.as_bytes()],
+            program_id,
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TokenAddedDictionary {
+    pub token_added: bool,
+}
+impl Sealed for TokenAddedDictionary {}
+impl Pack for TokenAddedDictionary {
+    const LEN: usize = 1;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, TokenAddedDictionary::LEN];
+        let token_added = match src {
+            [0] => false,
+            [1] => true,
+            _ => panic!(),
+        };
+        Ok(Self { token_added })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, TokenAddedDictionary::LEN];
+        dst[0] = self.token_added as u8;
+    }
+}
+
+impl TokenAddedDictionary {
+    pub fn generate_pda_key(
+        program_id: &Pubkey,
+        token_mint_address: &Pubkey,
+    ) -> (Pubkey, u8, String, String) {
+        let seed1 = "bridge";
+        let seed2 = "token_added_dictionary_key";
+        let (pda, bump) = Pubkey::find_program_address(
+            &[
+                token_mint_address.as_ref(),
+                seed1.as_bytes(),
+                seed2.as_bytes(),
+            ],
+            program_id,
+        );
+        (pda, bump, seed1.to_string(), seed2.to_string())
+    }
+}
+
+/// Recipient of a TransferRequest on another chain
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum RemoteAddress {
+    /// A 32 byte account hash, or any other 32 byte address
+    AccountHash([u8; 32]),
+    /// An ed25519 public key, 33 bytes with Casper's 0x01 tag
+    Ed25519([u8; 32]),
+    /// A compressed secp256k1 public key as its 0x02 or 0x03 prefix and x coordinate,
+    /// 34 bytes with Casper's 0x02 tag
+    Secp256k1(u8, [u8; 32]),
+}
+
+impl Default for RemoteAddress {
+    fn default() -> Self {
+        RemoteAddress::AccountHash([0; 32])
+    }
+}
+
+impl RemoteAddress {
+    pub const ACCOUNT_HASH: u8 = 1 << 0;
+    pub const ED25519: u8 = 1 << 1;
+    pub const SECP256K1: u8 = 1 << 2;
+    pub const ALL_KINDS: u8 =
+        RemoteAddress::ACCOUNT_HASH | RemoteAddress::ED25519 | RemoteAddress::SECP256K1;
+
+    /// Bit of the address' kind in a ChainRegistry's 'address_kinds'
+    pub fn kind(&self) -> u8 {
+        match self {
+            RemoteAddress::AccountHash(_) => RemoteAddress::ACCOUNT_HASH,
+            RemoteAddress::Ed25519(_) => RemoteAddress::ED25519,
+            RemoteAddress::Secp256k1(_, _) => RemoteAddress::SECP256K1,
+        }
+    }
+
+    /// Whether the address is well formed
+    pub fn is_valid(&self) -> bool {
+        match self {
+            RemoteAddress::Secp256k1(prefix, _) => *prefix == 2 || *prefix == 3,
+            _ => true,
+        }
+    }
+
+    /// Whether 'kinds' accepts at least one kind of address and no unknown ones
+    pub fn is_valid_kinds(kinds: u8) -> bool {
+        kinds != 0 && kinds & !RemoteAddress::ALL_KINDS == 0
+    }
+}
+
+impl Sealed for RemoteAddress {}
+impl Pack for RemoteAddress {
+    const LEN: usize = 1 + 1 + 32;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RemoteAddress::LEN];
+        let (kind_src, prefix_src, key_src) = array_refs![src, 1, 1, 32];
+        match (kind_src[0], prefix_src[0]) {
+            (RemoteAddress::ACCOUNT_HASH, 0) => Ok(RemoteAddress::AccountHash(*key_src)),
+            (RemoteAddress::ED25519, 0) => Ok(RemoteAddress::Ed25519(*key_src)),
+            (RemoteAddress::SECP256K1, prefix) => Ok(RemoteAddress::Secp256k1(prefix, *key_src)),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RemoteAddress::LEN];
+        let (kind_dst, prefix_dst, key_dst) = mut_array_refs![dst, 1, 1, 32];
+        kind_dst[0] = self.kind();
+        let (prefix, key) = match self {
+            RemoteAddress::AccountHash(key) | RemoteAddress::Ed25519(key) => (0, key),
+            RemoteAddress::Secp256k1(prefix, key) => (*prefix, key),
+        };
+        prefix_dst[0] = prefix;
+        key_dst.copy_from_slice(key);
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TransferRequestRecord {
+    pub is_initialized: bool, // 1B
+    pub token_index: u64,     // 8B
+    pub sender: Pubkey,       // 32B
+    pub mint: Pubkey,         // 32B
+    pub to: RemoteAddress,    // 34B
+    // gross amount received by the Bridge, fee included
+    pub amount: u64, // 8B
+    pub fee: u64,    // 8B
+    // destination chain
+    pub chain_id: u64,  // 8B
+    pub timestamp: u64, // 8B
+    // set once RefundRequest returned the amount to 'sender'
+    pub refunded: bool, // 1B
+}
+
+impl TransferRequestRecord {
+    /// Seed for all TransferRequestRecord PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "transfer_request_record_key";
+    pub fn generate_pda_key(program_id: &Pubkey, index: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                index.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                TransferRequestRecord::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Fee a refund of this record, made at 'index', returns. The fee is refunded in full
+    /// while it is still collected, and not at all once a withdrawal at 'fees_withdrawn_index'
+    /// took it to the treasury.
+    pub fn refundable_fee(&self, index: u64, fees_withdrawn_index: u64) -> u64 {
+        if index >= fees_withdrawn_index {
+            self.fee
+        } else {
+            0
+        }
+    }
+}
+
+impl Sealed for TransferRequestRecord {}
+impl Pack for TransferRequestRecord {
+    const LEN: usize = 1 + 8 + 32 + 32 + RemoteAddress::LEN + 8 + 8 + 8 + 8 + 1;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, TransferRequestRecord::LEN];
+        let (
+            is_initialized_src,
+            token_index_src,
+            sender_src,
+            mint_src,
+            to_src,
+            amount_src,
+            fee_src,
+            chain_id_src,
+            timestamp_src,
+            refunded_src,
+        ) = array_refs![src, 1, 8, 32, 32, RemoteAddress::LEN, 8, 8, 8, 8, 1];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let refunded = match refunded_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            token_index: u64::from_le_bytes(*token_index_src),
+            sender: Pubkey::new_from_array(*sender_src),
+            mint: Pubkey::new_from_array(*mint_src),
+            to: RemoteAddress::unpack_from_slice(to_src)?,
+            amount: u64::from_le_bytes(*amount_src),
+            fee: u64::from_le_bytes(*fee_src),
+            chain_id: u64::from_le_bytes(*chain_id_src),
+            timestamp: u64::from_le_bytes(*timestamp_src),
+            refunded,
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, TransferRequestRecord::LEN];
+        let (
+            is_initialized_dst,
+            token_index_dst,
+            sender_dst,
+            mint_dst,
+            to_dst,
+            amount_dst,
+            fee_dst,
+            chain_id_dst,
+            timestamp_dst,
+            refunded_dst,
+        ) = mut_array_refs![dst, 1, 8, 32, 32, RemoteAddress::LEN, 8, 8, 8, 8, 1];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *token_index_dst = self.token_index.to_le_bytes();
+        sender_dst.copy_from_slice(self.sender.as_ref());
+        mint_dst.copy_from_slice(self.mint.as_ref());
+        self.to.pack_into_slice(to_dst);
+        *amount_dst = self.amount.to_le_bytes();
+        *fee_dst = self.fee.to_le_bytes();
+        *chain_id_dst = self.chain_id.to_le_bytes();
+        *timestamp_dst = self.timestamp.to_le_bytes();
+        refunded_dst[0] = self.refunded as u8;
+    }
+}
+impl IsInitialized for TransferRequestRecord {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Roles that can run, or queue in the timelock, the Bridge's administrative instructions.
+/// Granted by the owner.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// UpdateStableFeeOnlyOwner, UpdateTokenFeeOnlyOwner, UpdateFeeScheduleOnlyOwner,
+    /// SetLamportFeeOnlyOwner, SetFeeRoundingOnlyOwner
+    FeeManager,
+    /// UpdateTokenLimitOnlyOwner, UpdateRecipientLimitOnlyOwner, SetTokenLimitWindowOnlyOwner,
+    /// SetOutboundLimitOnlyOwner, SetSettlementOnlyOwner
+    LimitManager,
+    /// PauseTokenOnlyOwner, UnpauseTokenOnlyOwner
+    Pauser,
+    /// AddTokenOnlyOwner, SetTokenMintBurnOnlyOwner, SetRemoteDecimalsOnlyOwner,
+    /// SetRemoteTokenOnlyOwner
+    TokenLister,
+    /// WithdrawFees, WithdrawFeesBatch, WithdrawLamportFees
+    Treasurer,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct RoleDictionary {
+    pub is_initialized: bool, // 1B
+    pub has_role: bool,       // 1B
+}
+
+impl RoleDictionary {
+    /// Seed for all RoleDictionary PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "role_dictionary_key";
+    pub fn generate_pda_key(program_id: &Pubkey, role: Role, account: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                &[role as u8],
+                account.as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                RoleDictionary::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+impl Sealed for RoleDictionary {}
+impl Pack for RoleDictionary {
+    const LEN: usize = 1 + 1;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, RoleDictionary::LEN];
+        let (is_initialized_src, has_role_src) = array_refs![src, 1, 1];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let has_role = match has_role_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            has_role,
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, RoleDictionary::LEN];
+        let (is_initialized_dst, has_role_dst) = mut_array_refs![dst, 1, 1];
+        is_initialized_dst[0] = self.is_initialized as u8;
+        has_role_dst[0] = self.has_role as u8;
+    }
+}
+impl IsInitialized for RoleDictionary {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+/// Maximum size of a Borsh encoded BridgeInstruction held by an AdminAction
+pub const MAX_ADMIN_ACTION_LEN: usize = 128;
+
+/// A timelocked admin instruction, executable by anyone once 'eta' has passed
+#[derive(Default, Debug, Clone)]
+pub struct AdminAction {
+    pub is_initialized: bool, // 1B
+    pub proposer: Pubkey,     // 32B
+    pub eta: u64,             // 8B
+    pub executed: bool,       // 1B
+    pub cancelled: bool,      // 1B
+    // Borsh encoded BridgeInstruction, at most MAX_ADMIN_ACTION_LEN bytes
+    pub instruction: Vec<u8>, // 2B length + MAX_ADMIN_ACTION_LEN
+}
+
+impl AdminAction {
+    /// Seed for all AdminAction PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "admin_action_key";
+    pub fn generate_pda_key(program_id: &Pubkey, action_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                action_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                AdminAction::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+impl Sealed for AdminAction {}
+impl Pack for AdminAction {
+    const LEN: usize = 1 + 32 + 8 + 1 + 1 + 2 + MAX_ADMIN_ACTION_LEN;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, AdminAction::LEN];
+        let (
+            is_initialized_src,
+            proposer_src,
+            eta_src,
+            executed_src,
+            cancelled_src,
+            instruction_len_src,
+            instruction_src,
+        ) = array_refs![src, 1, 32, 8, 1, 1, 2, MAX_ADMIN_ACTION_LEN];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let executed = match executed_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let cancelled = match cancelled_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let instruction_len = u16::from_le_bytes(*instruction_len_src) as usize;
+        if instruction_len > MAX_ADMIN_ACTION_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Self {
+            is_initialized,
+            proposer: Pubkey::new_from_array(*proposer_src),
+            eta: u64::from_le_bytes(*eta_src),
+            executed,
+            cancelled,
+            instruction: instruction_src[..instruction_len].to_vec(),
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, AdminAction::LEN];
+        let (
+            is_initialized_dst,
+            proposer_dst,
+            eta_dst,
+            executed_dst,
+            cancelled_dst,
+            instruction_len_dst,
+            instruction_dst,
+        ) = mut_array_refs![dst, 1, 32, 8, 1, 1, 2, MAX_ADMIN_ACTION_LEN];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        proposer_dst.copy_from_slice(self.proposer.as_ref());
+        *eta_dst = self.eta.to_le_bytes();
+        executed_dst[0] = self.executed as u8;
+        cancelled_dst[0] = self.cancelled as u8;
+        *instruction_len_dst = (self.instruction.len() as u16).to_le_bytes();
+        instruction_dst.fill(0);
+        instruction_dst[..self.instruction.len()].copy_from_slice(&self.instruction);
+    }
+}
+impl IsInitialized for AdminAction {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ChainRegistry {
+    pub is_initialized: bool, // 1B
+    pub chain_id: u64,        // 8B
+    pub enabled: bool,        // 1B
+    pub paused: bool,         // 1B
+    // overrides Bridge's stable fee for requests to this chain when non zero
+    pub stable_fee: u64, // 8B
+    // RemoteAddress kinds accepted as recipients on this chain, a mask of their 'kind' bits
+    pub address_kinds: u8, // 1B
+}
+
+impl ChainRegistry {
+    /// Seed for all ChainRegistry PDAs, alongwith COMMON_BASE_SEED
+    pub const BASE_SEED: &'static str = "chain_registry_key";
+    pub fn generate_pda_key(program_id: &Pubkey, chain_id: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                chain_id.to_le_bytes().as_ref(),
+                COMMON_BASE_SEED.as_bytes(),
+                ChainRegistry::BASE_SEED.as_bytes(),
+            ],
+            program_id,
+        )
+    }
+}
+
+impl Sealed for ChainRegistry {}
+impl Pack for ChainRegistry {
+    const LEN: usize = 1 + 8 + 1 + 1 + 8 + 1;
+
+    // for deserialization
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ChainRegistry::LEN];
+        let (
+            is_initialized_src,
+            chain_id_src,
+            enabled_src,
+            paused_src,
+            stable_fee_src,
+            address_kinds_src,
+        ) = array_refs![src, 1, 8, 1, 1, 8, 1];
+        let is_initialized = match is_initialized_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let enabled = match enabled_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        let paused = match paused_src {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidAccountData),
+        };
+        Ok(Self {
+            is_initialized,
+            chain_id: u64::from_le_bytes(*chain_id_src),
+            enabled,
+            paused,
+            stable_fee: u64::from_le_bytes(*stable_fee_src),
+            address_kinds: address_kinds_src[0],
+        })
+    }
+
+    // for serialization
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ChainRegistry::LEN];
+        let (
+            is_initialized_dst,
+            chain_id_dst,
+            enabled_dst,
+            paused_dst,
+            stable_fee_dst,
+            address_kinds_dst,
+        ) = mut_array_refs![dst, 1, 8, 1, 1, 8, 1];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        *chain_id_dst = self.chain_id.to_le_bytes();
+        enabled_dst[0] = self.enabled as u8;
+        paused_dst[0] = self.paused as u8;
+        *stable_fee_dst = self.stable_fee.to_le_bytes();
+        address_kinds_dst[0] = self.address_kinds;
+    }
+}
+impl IsInitialized for ChainRegistry {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer_request_record(amount: u64, fee: u64) -> TransferRequestRecord {
+        TransferRequestRecord {
+            is_initialized: true,
+            token_index: 1,
+            sender: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            to: RemoteAddress::Secp256k1(2, [3; 32]),
+            amount,
+            fee,
+            chain_id: 4,
+            timestamp: 5,
+            refunded: false,
+        }
+    }
+
+    #[test]
+    fn fee_is_refundable_until_withdrawn() {
+        let record_data = transfer_request_record(1_000, 10);
+        // request 5, fees last withdrawn before it
+        assert_eq!(record_data.refundable_fee(5, 0), 10);
+        assert_eq!(record_data.refundable_fee(5, 5), 10);
+        // withdrawn with the fees of requests up to 5
+        assert_eq!(record_data.refundable_fee(5, 6), 0);
+        assert_eq!(record_data.refundable_fee(5, u64::MAX), 0);
+        assert_eq!(transfer_request_record(1_000, 0).refundable_fee(5, 0), 0);
+    }
+
+    #[test]
+    fn transfer_request_record_round_trips() {
+        let mut record_data = transfer_request_record(1_000, 10);
+        record_data.refunded = true;
+        let mut packed = [0; TransferRequestRecord::LEN];
+        record_data.pack_into_slice(&mut packed);
+        let unpacked = TransferRequestRecord::unpack_from_slice(&packed).unwrap();
+        assert_eq!(unpacked.sender, record_data.sender);
+        assert_eq!(unpacked.mint, record_data.mint);
+        assert_eq!(unpacked.to, record_data.to);
+        assert_eq!(
+            (
+                unpacked.amount,
+                unpacked.fee,
+                unpacked.chain_id,
+                unpacked.timestamp
+            ),
+            (1_000, 10, 4, 5)
+        );
+        assert!(unpacked.is_initialized && unpacked.refunded);
+    }
+
+    const DAY: u64 = 24 * 3600;
+
+    #[test]
+    fn rate_limit_buckets_split_the_window() {
+        assert_eq!(RateLimitWindow::bucket(0, DAY), 0);
+        assert_eq!(RateLimitWindow::bucket(3599, DAY), 0);
+        assert_eq!(RateLimitWindow::bucket(3600, DAY), 1);
+        // windows shorter than RATE_LIMIT_BUCKETS seconds use one second buckets
+        assert_eq!(RateLimitWindow::bucket(7, 5), 7);
 
-        *fee_dst = fee.to_le_bytes();
+        assert!(RateLimitWindow::is_valid_window(DAY));
+        assert!(RateLimitWindow::is_valid_window(RATE_LIMIT_BUCKETS as u64));
+        assert!(!RateLimitWindow::is_valid_window(0));
+        assert!(!RateLimitWindow::is_valid_window(DAY + 1));
+    }
+
+    #[test]
+    fn rate_limit_amounts_roll_out_of_the_window() {
+        let mut window = RateLimitWindow::default();
+        window.roll(0, DAY);
+        window.record(100).unwrap();
+        window.roll(3600, DAY);
+        window.record(50).unwrap();
+        assert_eq!(window.window_total(), 150);
+
+        // an earlier timestamp doesn't move the window back
+        window.roll(0, DAY);
+        assert_eq!(window.last_bucket, 1);
+        assert_eq!(window.window_total(), 150);
+
+        // the first bucket falls out a full window after it started
+        window.roll(DAY, DAY);
+        assert_eq!(window.window_total(), 50);
+        window.roll(DAY + 3600, DAY);
+        assert_eq!(window.window_total(), 0);
+
+        window.record(10).unwrap();
+        window.roll(10 * DAY, DAY);
+        assert_eq!(window.window_total(), 0);
+        assert_eq!(window.last_bucket, 10 * 24);
+    }
+
+    #[test]
+    fn rate_limit_exceeds_only_above_the_limit() {
+        let mut window = RateLimitWindow::default();
+        window.record(60).unwrap();
+        assert!(!window.exceeds(40, 100));
+        assert!(window.exceeds(41, 100));
+        assert!(window.exceeds(u64::MAX, u64::MAX));
+        assert!(!window.exceeds(0, 60));
+
+        window.record(u64::MAX - 60).unwrap();
+        assert_eq!(
+            window.record(1).unwrap_err(),
+            ProgramError::Custom(BridgeError::Overflow as u32)
+        );
+    }
+
+    #[test]
+    fn rate_limit_set_window_carries_the_amounts_in_the_old_window() {
+        let mut window = RateLimitWindow::default();
+        window.roll(0, DAY);
+        window.record(100).unwrap();
+        window.roll(2 * 3600, DAY);
+        window.record(20).unwrap();
+
+        window.set_window(3 * 3600, DAY, 2 * DAY).unwrap();
+        assert_eq!(
+            window.last_bucket,
+            RateLimitWindow::bucket(3 * 3600, 2 * DAY)
+        );
+        assert_eq!(window.window_total(), 120);
+        // carried amounts roll out a full new window later
+        window.roll(3 * 3600 + 2 * DAY, 2 * DAY);
+        assert_eq!(window.window_total(), 0);
+
+        // amounts already outside the old window aren't carried
+        window.record(30).unwrap();
+        window.set_window(10 * DAY, 2 * DAY, DAY).unwrap();
+        assert_eq!(window.window_total(), 0);
+    }
+
+    #[test]
+    fn rate_limit_window_round_trips() {
+        let mut window = RateLimitWindow::default();
+        window.roll(5 * 3600, DAY);
+        window.record(7).unwrap();
+        let mut packed = [0; RateLimitWindow::LEN];
+        window.pack_into_slice(&mut packed);
+        let unpacked = RateLimitWindow::unpack_from_slice(&packed).unwrap();
+        assert_eq!(unpacked.last_bucket, 5);
+        assert_eq!(unpacked.buckets, window.buckets);
+    }
+
+    fn token_with_decimals(local_decimals: u8, remote_decimals: u8) -> TokenListDictionary {
+        TokenListDictionary {
+            local_decimals,
+            remote_decimals,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remote_amount_leaves_the_dust_locally() {
+        let token = token_with_decimals(9, 6);
+        assert_eq!(
+            token.to_remote_amount(1_234_567_891).unwrap(),
+            (1_234_567, 891)
+        );
+        assert_eq!(token.to_remote_amount(999).unwrap(), (0, 999));
+        assert_eq!(token.to_local_amount(1_234_567).unwrap(), 1_234_567_000);
+
+        let token = token_with_decimals(6, 6);
+        assert_eq!(token.to_remote_amount(1_234_567).unwrap(), (1_234_567, 0));
+        assert_eq!(token.to_local_amount(1_234_567).unwrap(), 1_234_567);
+    }
+
+    #[test]
+    fn local_amount_rounds_remote_dust_down() {
+        let token = token_with_decimals(6, 18);
+        assert_eq!(
+            token.to_remote_amount(1_500_000).unwrap(),
+            (1_500_000_000_000_000_000, 0)
+        );
+        assert_eq!(
+            token.to_local_amount(1_500_000_999_999_999_999).unwrap(),
+            1_500_000
+        );
+        assert_eq!(token.to_local_amount(999_999_999_999).unwrap(), 0);
+    }
+
+    #[test]
+    fn decimal_scaling_fails_instead_of_overflowing() {
+        assert_eq!(decimals_factor(0).unwrap(), 1);
+        assert_eq!(decimals_factor(19).unwrap(), 10_000_000_000_000_000_000);
+        assert_eq!(
+            decimals_factor(20).unwrap_err(),
+            ProgramError::Custom(BridgeError::InvalidDecimals as u32)
+        );
+
+        let overflow = ProgramError::Custom(BridgeError::Overflow as u32);
+        assert_eq!(
+            token_with_decimals(0, 18)
+                .to_remote_amount(u64::MAX)
+                .unwrap_err(),
+            overflow
+        );
+        assert_eq!(
+            token_with_decimals(18, 0)
+                .to_local_amount(u64::MAX)
+                .unwrap_err(),
+            overflow
+        );
+        assert_eq!(
+            token_with_decimals(0, 30).to_remote_amount(1).unwrap_err(),
+            ProgramError::Custom(BridgeError::InvalidDecimals as u32)
+        );
+    }
+
+    #[test]
+    fn remote_addresses_are_validated_by_kind() {
+        assert!(RemoteAddress::AccountHash([1; 32]).is_valid());
+        assert!(RemoteAddress::Ed25519([1; 32]).is_valid());
+        assert!(RemoteAddress::Secp256k1(2, [1; 32]).is_valid());
+        assert!(RemoteAddress::Secp256k1(3, [1; 32]).is_valid());
+        assert!(!RemoteAddress::Secp256k1(4, [1; 32]).is_valid());
+        assert!(!RemoteAddress::Secp256k1(0, [1; 32]).is_valid());
+
+        assert_eq!(RemoteAddress::default().kind(), RemoteAddress::ACCOUNT_HASH);
+        assert_eq!(
+            RemoteAddress::Ed25519([1; 32]).kind(),
+            RemoteAddress::ED25519
+        );
+        assert_eq!(
+            RemoteAddress::Secp256k1(2, [1; 32]).kind(),
+            RemoteAddress::SECP256K1
+        );
+
+        assert!(RemoteAddress::is_valid_kinds(RemoteAddress::ALL_KINDS));
+        assert!(RemoteAddress::is_valid_kinds(RemoteAddress::ED25519));
+        assert!(!RemoteAddress::is_valid_kinds(0));
+        assert!(!RemoteAddress::is_valid_kinds(
+            RemoteAddress::ED25519 | 1 << 3
+        ));
+    }
+
+    #[test]
+    fn remote_addresses_round_trip() {
+        for address in [
+            RemoteAddress::AccountHash([1; 32]),
+            RemoteAddress::Ed25519([2; 32]),
+            RemoteAddress::Secp256k1(3, [4; 32]),
+        ] {
+            let mut packed = [0; RemoteAddress::LEN];
+            address.pack_into_slice(&mut packed);
+            assert_eq!(packed[0], address.kind());
+            assert_eq!(RemoteAddress::unpack_from_slice(&packed).unwrap(), address);
+        }
+
+        // unknown kinds, and a prefix on a kind without one, don't unpack
+        let mut packed = [0; RemoteAddress::LEN];
+        assert_eq!(
+            RemoteAddress::unpack_from_slice(&packed).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+        packed[0] = RemoteAddress::ED25519;
+        packed[1] = 2;
+        assert_eq!(
+            RemoteAddress::unpack_from_slice(&packed).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
+    }
+
+    fn fee_tiers(tiers: &[(u64, u64)]) -> Vec<FeeTier> {
+        tiers
+            .iter()
+            .map(|&(threshold, rate)| FeeTier { threshold, rate })
+            .collect()
+    }
+
+    #[test]
+    fn fee_schedule_rate_is_of_the_last_tier_reached() {
+        let schedule = FeeSchedule {
+            tiers: fee_tiers(&[(0, 30), (1_000, 20), (10_000, 10)]),
+            ..Default::default()
+        };
+        assert_eq!(schedule.rate(0), 30);
+        assert_eq!(schedule.rate(999), 30);
+        assert_eq!(schedule.rate(1_000), 20);
+        assert_eq!(schedule.rate(u64::MAX), 10);
+        assert_eq!(FeeSchedule::default().rate(1_000), 0);
+    }
+
+    #[test]
+    fn fee_schedule_round_trips() {
+        let schedule = FeeSchedule {
+            is_initialized: true,
+            tiers: fee_tiers(&[(0, 30), (1_000, 20)]),
+            min_fee: 1,
+            max_fee: 500,
+            update_time: 7,
+            new_tiers: fee_tiers(&[(0, 10); MAX_FEE_TIERS]),
+            new_min_fee: 2,
+            new_max_fee: 0,
+        };
+        let mut packed = vec![0; FeeSchedule::LEN];
+        schedule.pack_into_slice(&mut packed);
+        let unpacked = FeeSchedule::unpack_from_slice(&packed).unwrap();
+        let pairs = |tiers: &[FeeTier]| -> Vec<(u64, u64)> {
+            tiers
+                .iter()
+                .map(|tier| (tier.threshold, tier.rate))
+                .collect()
+        };
+        assert_eq!(pairs(&unpacked.tiers), vec![(0, 30), (1_000, 20)]);
+        assert_eq!(pairs(&unpacked.new_tiers), vec![(0, 10); MAX_FEE_TIERS]);
+        assert_eq!(
+            (
+                unpacked.min_fee,
+                unpacked.max_fee,
+                unpacked.update_time,
+                unpacked.new_min_fee,
+                unpacked.new_max_fee
+            ),
+            (1, 500, 7, 2, 0)
+        );
+
+        // more tiers than MAX_FEE_TIERS don't unpack
+        packed[1] = MAX_FEE_TIERS as u8 + 1;
+        assert_eq!(
+            FeeSchedule::unpack_from_slice(&packed).unwrap_err(),
+            ProgramError::InvalidAccountData
+        );
     }
 }
@@ -14,6 +14,10 @@ pub enum WPoktError {
     AlreadySet,
     #[error("Invalid Caller.")]
     InvalidCaller,
+    #[error("No pending owner.")]
+    NoPendingOwner,
+    #[error("Renounce not confirmed.")]
+    RenounceNotConfirmed,
 }
 
 impl From<WPoktError> for ProgramError {
@@ -27,12 +27,24 @@ pub enum WPoktInstruction {
     /// 1. `[signer]` the 0th token account's owner/delegate
     /// 2. `[writable]` the mint account
     Burn { amount: u64 },
+    /// Leaves WPokt without an owner for good, 'confirm' must be true.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The program owner's account.
     /// 1. `[writeable]` The account used as WPokt's global state
-    RenounceOwnership,
+    RenounceOwnership { confirm: bool },
+    /// Proposes 'new_owner', who takes over once it signs AcceptOwnership.
+    ///
     /// Accounts expected:
     /// 0. `[signer]` The program owner's account.
     /// 1. `[writeable]` The account used as WPokt's global state
-    TransferOwnership { new_owner: Pubkey },
+    ProposeOwner { new_owner: Pubkey },
+    /// Accounts expected:
+    /// 0. `[signer]` The pending owner's account.
+    /// 1. `[writeable]` The account used as WPokt's global state
+    AcceptOwnership,
+    /// Accounts expected:
+    /// 0. `[signer]` The program owner's account.
+    /// 1. `[writeable]` The account used as WPokt's global state
+    CancelOwnershipProposal,
 }
@@ -40,9 +40,15 @@ impl Processor {
                 mint(program_id, accounts, amount)
             }
             WPoktInstruction::Burn { amount } => burn(program_id, accounts, amount),
-            WPoktInstruction::RenounceOwnership => renounce_ownership(program_id, accounts),
-            WPoktInstruction::TransferOwnership { new_owner } => {
-                transfer_ownership(program_id, accounts, new_owner)
+            WPoktInstruction::RenounceOwnership { confirm } => {
+                renounce_ownership(program_id, accounts, confirm)
+            }
+            WPoktInstruction::ProposeOwner { new_owner } => {
+                propose_owner(program_id, accounts, new_owner)
+            }
+            WPoktInstruction::AcceptOwnership => accept_ownership(program_id, accounts),
+            WPoktInstruction::CancelOwnershipProposal => {
+                cancel_ownership_proposal(program_id, accounts)
             }
         }
     }
@@ -232,7 +238,11 @@ fn burn(_program_id: &Pubkey, _accounts: &[AccountInfo], _amount: u64) -> Progra
     Ok(())
 }
 
-fn renounce_ownership(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+fn renounce_ownership(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _confirm: bool,
+) -> ProgramResult {
     let account_info_iter = &mut _accounts.iter();
     let owner_account = next_account_info(account_info_iter)?;
     let wpokt_account = next_account_info(account_info_iter)?;
@@ -253,12 +263,19 @@ fn renounce_ownership(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> Progra
         return Err(ProgramError::IllegalOwner);
     }
 
+    if !_confirm {
+        return Err(ProgramError::Custom(
+            WPoktError::RenounceNotConfirmed as u32,
+        ));
+    }
+
     wpokt_data.owner = Pubkey::new_from_array([0_u8; 32]);
-    wpokt_data.pack_into_slice(&mut &mut wpokt_account.data.borrow_mut()[..]);
+    wpokt_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    wpokt_data.pack_into_slice(&mut wpokt_account.data.borrow_mut()[..]);
     Ok(())
 }
 
-fn transfer_ownership(
+fn propose_owner(
     _program_id: &Pubkey,
     _accounts: &[AccountInfo],
     _new_owner: Pubkey,
@@ -266,16 +283,11 @@ fn transfer_ownership(
     let account_info_iter = &mut _accounts.iter();
     let owner_account = next_account_info(account_info_iter)?;
     let wpokt_account = next_account_info(account_info_iter)?;
-    let new_owner_account = next_account_info(account_info_iter)?;
 
     if wpokt_account.owner != _program_id {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    if *new_owner_account.key != _new_owner {
-        return Err(ProgramError::InvalidInstructionData);
-    }
-
     let mut wpokt_data = WPokt::unpack_from_slice(&wpokt_account.data.borrow())?;
     if !wpokt_data.is_initialized {
         return Err(ProgramError::UninitializedAccount);
@@ -292,7 +304,69 @@ fn transfer_ownership(
     if _new_owner == Pubkey::new_from_array([0_u8; 32]) {
         return Err(ProgramError::InvalidArgument);
     }
-    wpokt_data.owner = _new_owner;
-    wpokt_data.pack_into_slice(&mut &mut wpokt_account.data.borrow_mut()[..]);
+    wpokt_data.pending_owner = _new_owner;
+    wpokt_data.pack_into_slice(&mut wpokt_account.data.borrow_mut()[..]);
+    Ok(())
+}
+
+fn accept_ownership(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let pending_owner_account = next_account_info(account_info_iter)?;
+    let wpokt_account = next_account_info(account_info_iter)?;
+
+    if wpokt_account.owner != _program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut wpokt_data = WPokt::unpack_from_slice(&wpokt_account.data.borrow())?;
+    if !wpokt_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if wpokt_data.pending_owner == Pubkey::new_from_array([0_u8; 32]) {
+        return Err(ProgramError::Custom(WPoktError::NoPendingOwner as u32));
+    }
+
+    // only pending owner
+    if !pending_owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if wpokt_data.pending_owner != *pending_owner_account.key {
+        return Err(ProgramError::Custom(WPoktError::InvalidCaller as u32));
+    }
+
+    wpokt_data.owner = wpokt_data.pending_owner;
+    wpokt_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    wpokt_data.pack_into_slice(&mut wpokt_account.data.borrow_mut()[..]);
+    Ok(())
+}
+
+fn cancel_ownership_proposal(_program_id: &Pubkey, _accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut _accounts.iter();
+    let owner_account = next_account_info(account_info_iter)?;
+    let wpokt_account = next_account_info(account_info_iter)?;
+
+    if wpokt_account.owner != _program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut wpokt_data = WPokt::unpack_from_slice(&wpokt_account.data.borrow())?;
+    if !wpokt_data.is_initialized {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    // only owner
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if wpokt_data.owner != *owner_account.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    if wpokt_data.pending_owner == Pubkey::new_from_array([0_u8; 32]) {
+        return Err(ProgramError::Custom(WPoktError::NoPendingOwner as u32));
+    }
+    wpokt_data.pending_owner = Pubkey::new_from_array([0_u8; 32]);
+    wpokt_data.pack_into_slice(&mut wpokt_account.data.borrow_mut()[..]);
     Ok(())
 }